@@ -36,17 +36,24 @@ enum AnimationFieldType {
     Integer,
     Float,
     String,
+    Vec2,
+    Size,
+    Color,
 }
 
 struct AnimationFieldArgument {
     pub field: LitStr,
     pub ty: AnimationFieldType,
+    pub member: Option<LitStr>,
+    pub set: Option<Ident>,
 }
 
 impl Parse for AnimationFieldArgument {
     fn parse(input: ParseStream) -> SynResult<Self> {
         let mut field = None;
         let mut ty = None;
+        let mut member = None;
+        let mut set = None;
 
         while !input.is_empty() {
             let arg_name = input.parse::<Ident>()?;
@@ -76,6 +83,9 @@ impl Parse for AnimationFieldArgument {
                     "integer" => AnimationFieldType::Integer,
                     "float" => AnimationFieldType::Float,
                     "string" => AnimationFieldType::String,
+                    "vec2" => AnimationFieldType::Vec2,
+                    "size" => AnimationFieldType::Size,
+                    "color" => AnimationFieldType::Color,
                     ty @ _ => {
                         return Err(SynError::new(
                             parsed.span(),
@@ -84,6 +94,28 @@ impl Parse for AnimationFieldArgument {
                     }
                 });
 
+                if input.peek(Comma) {
+                    input.parse::<Comma>()?;
+                }
+            } else if arg_name == "member" {
+                if member.is_some() {
+                    return Err(SynError::new(input.span(), "duplicated argument: member"));
+                }
+
+                input.parse::<Token![=]>()?;
+                member = Some(input.parse::<LitStr>()?);
+
+                if input.peek(Comma) {
+                    input.parse::<Comma>()?;
+                }
+            } else if arg_name == "set" {
+                if set.is_some() {
+                    return Err(SynError::new(input.span(), "duplicated argument: set"));
+                }
+
+                input.parse::<Token![=]>()?;
+                set = Some(input.parse::<Ident>()?);
+
                 if input.peek(Comma) {
                     input.parse::<Comma>()?;
                 }
@@ -95,6 +127,13 @@ impl Parse for AnimationFieldArgument {
             }
         }
 
+        if member.is_some() && set.is_some() {
+            return Err(SynError::new(
+                input.span(),
+                "member and set arguments cannot be used together",
+            ));
+        }
+
         let field = if let Some(field) = field {
             field
         } else {
@@ -107,7 +146,12 @@ impl Parse for AnimationFieldArgument {
             return Err(SynError::new(input.span(), "missing argument: ty"));
         };
 
-        Ok(Self { field, ty })
+        Ok(Self {
+            field,
+            ty,
+            member,
+            set,
+        })
     }
 }
 
@@ -123,14 +167,16 @@ pub fn animation_derive(item: TokenStream) -> TokenStream {
 
     let name = input.ident;
     let name_snake = name.to_string().to_case(Case::Snake);
-    let mut field_index = 0;
     let mut field_set = HashSet::new();
-    let mut fields = vec![];
     let mut animation_fields = vec![];
-    let mut matching_tys = vec![];
-    let mut matching_as_tys = vec![];
+    let mut assignments = vec![];
+
+    for (field_index, field) in data.fields.iter().enumerate() {
+        let field_ref = match &field.ident {
+            Some(ident) => FieldRef::Ident(ident.clone()),
+            None => FieldRef::Index(Index::from(field_index)),
+        };
 
-    for field in &data.fields {
         for attr in &field.attrs {
             if let Some(ident) = attr.path.get_ident() {
                 if ident == "animate" {
@@ -150,36 +196,73 @@ pub fn animation_derive(item: TokenStream) -> TokenStream {
                         field_set.insert(argument_field);
                     }
 
-                    match &field.ident {
-                        Some(ident) => {
-                            fields.push(FieldRef::Ident(ident.clone()));
-                        }
-                        None => {
-                            let index = Index::from(field_index);
-                            field_index += 1;
-                            fields.push(FieldRef::Index(index));
-                        }
-                    };
-
-                    animation_fields.push(argument.field);
-                    matching_tys.push(format_ident!(
-                        "{}",
-                        match &argument.ty {
-                            AnimationFieldType::Bool => "bool",
-                            AnimationFieldType::Integer => "i64",
-                            AnimationFieldType::Float => "f64",
-                            AnimationFieldType::String => "String",
-                        }
-                    ));
-                    matching_as_tys.push(format_ident!(
+                    let matching_as_ty = format_ident!(
                         "as_{}",
                         match &argument.ty {
                             AnimationFieldType::Bool => "bool",
                             AnimationFieldType::Integer => "integer",
                             AnimationFieldType::Float => "float",
                             AnimationFieldType::String => "string",
+                            AnimationFieldType::Vec2 => "vec2",
+                            AnimationFieldType::Size => "size",
+                            AnimationFieldType::Color => "color",
                         }
-                    ));
+                    );
+                    let value = match &argument.ty {
+                        // Colors are interpolated in the color space specified by the key frame.
+                        AnimationFieldType::Color => quote! {
+                            key_frame.color_space.interpolate(
+                                key_frame.from.#matching_as_ty(),
+                                key_frame.to.#matching_as_ty(),
+                                normalized_time_in_key_frame,
+                            )
+                        },
+                        ty => {
+                            let matching_ty = match ty {
+                                AnimationFieldType::Bool => quote! { bool },
+                                AnimationFieldType::Integer => quote! { i64 },
+                                AnimationFieldType::Float => quote! { f64 },
+                                AnimationFieldType::String => quote! { String },
+                                AnimationFieldType::Vec2 => quote! { crate::structure::Vec2 },
+                                AnimationFieldType::Size => quote! { crate::structure::Size },
+                                AnimationFieldType::Color => unreachable!(),
+                            };
+                            let value = quote! {
+                                <#matching_ty as crate::animation::Interpolatable>::interpolate(
+                                    key_frame.from.#matching_as_ty(),
+                                    key_frame.to.#matching_as_ty(),
+                                    normalized_time_in_key_frame,
+                                )
+                            };
+
+                            match ty {
+                                AnimationFieldType::Integer | AnimationFieldType::Float => {
+                                    quote! { #value as _ }
+                                }
+                                _ => value,
+                            }
+                        }
+                    };
+
+                    assignments.push(match (&argument.member, &argument.set) {
+                        (Some(member), _) => {
+                            let members = member
+                                .value()
+                                .split('.')
+                                .map(|member| format_ident!("{}", member))
+                                .collect::<Vec<_>>();
+                            quote! {
+                                self.#field_ref #(.#members)* = #value;
+                            }
+                        }
+                        (None, Some(set)) => quote! {
+                            self.#set(#value);
+                        },
+                        (None, None) => quote! {
+                            self.#field_ref = #value;
+                        },
+                    });
+                    animation_fields.push(argument.field);
                 }
             }
         }
@@ -196,18 +279,15 @@ pub fn animation_derive(item: TokenStream) -> TokenStream {
                 time_line: &crate::animation::AnimationTimeLine,
                 key_frame: &crate::animation::AnimationKeyFrame,
                 normalized_time_in_key_frame: f32,
-            ) {
+            ) -> bool {
                 match time_line.field.as_str() {
                     #(
                         #animation_fields => {
-                            self.#fields = <#matching_tys as crate::animation::Interpolatable>::interpolate(
-                                key_frame.from.#matching_as_tys(),
-                                key_frame.to.#matching_as_tys(),
-                                normalized_time_in_key_frame,
-                            ) as _;
+                            #assignments
+                            true
                         }
                     )*
-                    _ => {}
+                    _ => false,
                 }
             }
         }
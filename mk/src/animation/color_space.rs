@@ -0,0 +1,130 @@
+use crate::animation::Interpolatable;
+use crate::render::Color;
+use mlua::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AnimationColorSpace {
+    // Interpolates each of the RGBA components linearly.
+    #[default]
+    Linear,
+    // Interpolates hue, saturation and value, taking the shorter way around the hue circle.
+    Hsv,
+}
+
+impl AnimationColorSpace {
+    pub fn interpolate(self, lhs: Color, rhs: Color, t: f32) -> Color {
+        match self {
+            Self::Linear => Color::interpolate(lhs, rhs, t),
+            Self::Hsv => {
+                let (lhs_h, lhs_s, lhs_v) = rgb_to_hsv(lhs.r, lhs.g, lhs.b);
+                let (rhs_h, rhs_s, rhs_v) = rgb_to_hsv(rhs.r, rhs.g, rhs.b);
+
+                // Achromatic colors have no meaningful hue, so the other side's hue is kept.
+                let lhs_h = if lhs_s == 0f32 { rhs_h } else { lhs_h };
+                let rhs_h = if rhs_s == 0f32 { lhs_h } else { rhs_h };
+
+                let mut delta = rhs_h - lhs_h;
+
+                if 180f32 < delta {
+                    delta -= 360f32;
+                } else if delta < -180f32 {
+                    delta += 360f32;
+                }
+
+                let (r, g, b) = hsv_to_rgb(
+                    (lhs_h + delta * t).rem_euclid(360f32),
+                    f32::interpolate(lhs_s, rhs_s, t),
+                    f32::interpolate(lhs_v, rhs_v, t),
+                );
+
+                Color {
+                    r,
+                    g,
+                    b,
+                    a: f32::interpolate(lhs.a, rhs.a, t),
+                }
+            }
+        }
+    }
+}
+
+impl<'lua> FromLua<'lua> for AnimationColorSpace {
+    fn from_lua(value: LuaValue<'lua>, lua: &'lua Lua) -> LuaResult<Self> {
+        let str = String::from_lua(value, lua)?;
+        let str = str.as_str();
+        match str {
+            "linear" => Ok(Self::Linear),
+            "hsv" => Ok(Self::Hsv),
+            _ => Err(format!(
+                "{:?} is invalid value for the type {}",
+                str, "AnimationColorSpace",
+            )
+            .to_lua_err()),
+        }
+    }
+}
+
+fn rgb_to_hsv(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let h = if delta == 0f32 {
+        0f32
+    } else if max == r {
+        60f32 * ((g - b) / delta).rem_euclid(6f32)
+    } else if max == g {
+        60f32 * ((b - r) / delta + 2f32)
+    } else {
+        60f32 * ((r - g) / delta + 4f32)
+    };
+    let s = if max == 0f32 { 0f32 } else { delta / max };
+
+    (h, s, max)
+}
+
+fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (f32, f32, f32) {
+    let c = v * s;
+    let x = c * (1f32 - ((h / 60f32).rem_euclid(2f32) - 1f32).abs());
+    let m = v - c;
+
+    let (r, g, b) = match (h / 60f32) as u32 {
+        0 => (c, x, 0f32),
+        1 => (x, c, 0f32),
+        2 => (0f32, c, x),
+        3 => (0f32, x, c),
+        4 => (x, 0f32, c),
+        _ => (c, 0f32, x),
+    };
+
+    (r + m, g + m, b + m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hsv_interpolation_takes_shorter_hue_path() {
+        let red = Color {
+            r: 1f32,
+            g: 0f32,
+            b: 0f32,
+            a: 1f32,
+        };
+        let magenta = Color {
+            r: 1f32,
+            g: 0f32,
+            b: 1f32,
+            a: 1f32,
+        };
+        let color = AnimationColorSpace::Hsv.interpolate(red, magenta, 0.5f32);
+
+        // Halfway between 0 and 300 degrees through 360 is 330 degrees.
+        assert!((color.r - 1f32).abs() < 1e-4f32);
+        assert!(color.g.abs() < 1e-4f32);
+        assert!((color.b - 0.5f32).abs() < 1e-4f32);
+    }
+}
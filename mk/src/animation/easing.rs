@@ -0,0 +1,381 @@
+use mlua::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AnimationEasing {
+    #[default]
+    Linear,
+    // Holds the starting value until the key frame ends.
+    Constant,
+    // Jumps through the given number of evenly spaced steps.
+    Step(u32),
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuartIn,
+    QuartOut,
+    QuartInOut,
+    QuintIn,
+    QuintOut,
+    QuintInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    CircIn,
+    CircOut,
+    CircInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+    // Control points (x1, y1, x2, y2) of a CSS-like cubic bezier curve.
+    CubicBezier(f32, f32, f32, f32),
+}
+
+impl AnimationEasing {
+    // Maps the normalized time in [0, 1] to the interpolation factor; some easings overshoot the range.
+    pub fn ease(self, t: f32) -> f32 {
+        let t = t.clamp(0f32, 1f32);
+
+        match self {
+            Self::Linear => t,
+            Self::Constant => {
+                if t < 1f32 {
+                    0f32
+                } else {
+                    1f32
+                }
+            }
+            Self::Step(steps) => {
+                if steps == 0 {
+                    Self::Constant.ease(t)
+                } else {
+                    (t * steps as f32).floor() / steps as f32
+                }
+            }
+            Self::QuadIn => power_in(t, 2),
+            Self::QuadOut => power_out(t, 2),
+            Self::QuadInOut => power_in_out(t, 2),
+            Self::CubicIn => power_in(t, 3),
+            Self::CubicOut => power_out(t, 3),
+            Self::CubicInOut => power_in_out(t, 3),
+            Self::QuartIn => power_in(t, 4),
+            Self::QuartOut => power_out(t, 4),
+            Self::QuartInOut => power_in_out(t, 4),
+            Self::QuintIn => power_in(t, 5),
+            Self::QuintOut => power_out(t, 5),
+            Self::QuintInOut => power_in_out(t, 5),
+            Self::SineIn => 1f32 - (t * PI * 0.5f32).cos(),
+            Self::SineOut => (t * PI * 0.5f32).sin(),
+            Self::SineInOut => -((t * PI).cos() - 1f32) * 0.5f32,
+            Self::ExpoIn => {
+                if t == 0f32 {
+                    0f32
+                } else {
+                    2f32.powf(10f32 * t - 10f32)
+                }
+            }
+            Self::ExpoOut => {
+                if t == 1f32 {
+                    1f32
+                } else {
+                    1f32 - 2f32.powf(-10f32 * t)
+                }
+            }
+            Self::ExpoInOut => {
+                if t == 0f32 || t == 1f32 {
+                    t
+                } else if t < 0.5f32 {
+                    2f32.powf(20f32 * t - 10f32) * 0.5f32
+                } else {
+                    (2f32 - 2f32.powf(-20f32 * t + 10f32)) * 0.5f32
+                }
+            }
+            Self::CircIn => 1f32 - (1f32 - t * t).sqrt(),
+            Self::CircOut => (1f32 - (t - 1f32) * (t - 1f32)).sqrt(),
+            Self::CircInOut => {
+                if t < 0.5f32 {
+                    (1f32 - (1f32 - 4f32 * t * t).sqrt()) * 0.5f32
+                } else {
+                    let u = -2f32 * t + 2f32;
+                    ((1f32 - u * u).sqrt() + 1f32) * 0.5f32
+                }
+            }
+            Self::BackIn => {
+                let c1 = 1.70158f32;
+                (c1 + 1f32) * t * t * t - c1 * t * t
+            }
+            Self::BackOut => {
+                let c1 = 1.70158f32;
+                let u = t - 1f32;
+                1f32 + (c1 + 1f32) * u * u * u + c1 * u * u
+            }
+            Self::BackInOut => {
+                let c2 = 1.70158f32 * 1.525f32;
+
+                if t < 0.5f32 {
+                    let u = 2f32 * t;
+                    u * u * ((c2 + 1f32) * u - c2) * 0.5f32
+                } else {
+                    let u = 2f32 * t - 2f32;
+                    (u * u * ((c2 + 1f32) * u + c2) + 2f32) * 0.5f32
+                }
+            }
+            Self::ElasticIn => {
+                if t == 0f32 || t == 1f32 {
+                    t
+                } else {
+                    -(2f32.powf(10f32 * t - 10f32))
+                        * ((10f32 * t - 10.75f32) * (2f32 * PI / 3f32)).sin()
+                }
+            }
+            Self::ElasticOut => {
+                if t == 0f32 || t == 1f32 {
+                    t
+                } else {
+                    2f32.powf(-10f32 * t) * ((10f32 * t - 0.75f32) * (2f32 * PI / 3f32)).sin()
+                        + 1f32
+                }
+            }
+            Self::ElasticInOut => {
+                let c5 = 2f32 * PI / 4.5f32;
+
+                if t == 0f32 || t == 1f32 {
+                    t
+                } else if t < 0.5f32 {
+                    -(2f32.powf(20f32 * t - 10f32) * ((20f32 * t - 11.125f32) * c5).sin()) * 0.5f32
+                } else {
+                    2f32.powf(-20f32 * t + 10f32) * ((20f32 * t - 11.125f32) * c5).sin() * 0.5f32
+                        + 1f32
+                }
+            }
+            Self::BounceIn => 1f32 - bounce_out(1f32 - t),
+            Self::BounceOut => bounce_out(t),
+            Self::BounceInOut => {
+                if t < 0.5f32 {
+                    (1f32 - bounce_out(1f32 - 2f32 * t)) * 0.5f32
+                } else {
+                    (1f32 + bounce_out(2f32 * t - 1f32)) * 0.5f32
+                }
+            }
+            Self::CubicBezier(x1, y1, x2, y2) => {
+                cubic_bezier(t, x1.clamp(0f32, 1f32), y1, x2.clamp(0f32, 1f32), y2)
+            }
+        }
+    }
+}
+
+// Named easings are strings, the same as in animation files; `{ step = 4 }` and
+// `{ cubic_bezier = { x1, y1, x2, y2 } }` take parameters.
+impl<'lua> FromLua<'lua> for AnimationEasing {
+    fn from_lua(value: LuaValue<'lua>, lua: &'lua Lua) -> LuaResult<Self> {
+        match value {
+            LuaValue::Table(table) => {
+                if let Some(steps) = table.get::<_, Option<u32>>("step")? {
+                    return Ok(Self::Step(steps));
+                }
+
+                if let Some((x1, y1, x2, y2)) = table
+                    .get::<_, Option<LuaTable>>("cubic_bezier")?
+                    .map(|points| -> LuaResult<_> {
+                        Ok((
+                            points.get(1)?,
+                            points.get(2)?,
+                            points.get(3)?,
+                            points.get(4)?,
+                        ))
+                    })
+                    .transpose()?
+                {
+                    return Ok(Self::CubicBezier(x1, y1, x2, y2));
+                }
+
+                Err(format!(
+                    "the type {} must be a string, {{ step = n }} or {{ cubic_bezier = {{ x1, y1, x2, y2 }} }}",
+                    "AnimationEasing"
+                )
+                .to_lua_err())
+            }
+            value => {
+                let str = String::from_lua(value, lua)?;
+                let str = str.as_str();
+                match str {
+                    "linear" => Ok(Self::Linear),
+                    "constant" => Ok(Self::Constant),
+                    "quad_in" => Ok(Self::QuadIn),
+                    "quad_out" => Ok(Self::QuadOut),
+                    "quad_in_out" => Ok(Self::QuadInOut),
+                    "cubic_in" => Ok(Self::CubicIn),
+                    "cubic_out" => Ok(Self::CubicOut),
+                    "cubic_in_out" => Ok(Self::CubicInOut),
+                    "quart_in" => Ok(Self::QuartIn),
+                    "quart_out" => Ok(Self::QuartOut),
+                    "quart_in_out" => Ok(Self::QuartInOut),
+                    "quint_in" => Ok(Self::QuintIn),
+                    "quint_out" => Ok(Self::QuintOut),
+                    "quint_in_out" => Ok(Self::QuintInOut),
+                    "sine_in" => Ok(Self::SineIn),
+                    "sine_out" => Ok(Self::SineOut),
+                    "sine_in_out" => Ok(Self::SineInOut),
+                    "expo_in" => Ok(Self::ExpoIn),
+                    "expo_out" => Ok(Self::ExpoOut),
+                    "expo_in_out" => Ok(Self::ExpoInOut),
+                    "circ_in" => Ok(Self::CircIn),
+                    "circ_out" => Ok(Self::CircOut),
+                    "circ_in_out" => Ok(Self::CircInOut),
+                    "back_in" => Ok(Self::BackIn),
+                    "back_out" => Ok(Self::BackOut),
+                    "back_in_out" => Ok(Self::BackInOut),
+                    "elastic_in" => Ok(Self::ElasticIn),
+                    "elastic_out" => Ok(Self::ElasticOut),
+                    "elastic_in_out" => Ok(Self::ElasticInOut),
+                    "bounce_in" => Ok(Self::BounceIn),
+                    "bounce_out" => Ok(Self::BounceOut),
+                    "bounce_in_out" => Ok(Self::BounceInOut),
+                    _ => Err(format!(
+                        "{:?} is invalid value for the type {}",
+                        str, "AnimationEasing"
+                    )
+                    .to_lua_err()),
+                }
+            }
+        }
+    }
+}
+
+fn power_in(t: f32, power: i32) -> f32 {
+    t.powi(power)
+}
+
+fn power_out(t: f32, power: i32) -> f32 {
+    1f32 - (1f32 - t).powi(power)
+}
+
+fn power_in_out(t: f32, power: i32) -> f32 {
+    if t < 0.5f32 {
+        2f32.powi(power - 1) * t.powi(power)
+    } else {
+        1f32 - (-2f32 * t + 2f32).powi(power) * 0.5f32
+    }
+}
+
+fn bounce_out(t: f32) -> f32 {
+    let n1 = 7.5625f32;
+    let d1 = 2.75f32;
+
+    if t < 1f32 / d1 {
+        n1 * t * t
+    } else if t < 2f32 / d1 {
+        let t = t - 1.5f32 / d1;
+        n1 * t * t + 0.75f32
+    } else if t < 2.5f32 / d1 {
+        let t = t - 2.25f32 / d1;
+        n1 * t * t + 0.9375f32
+    } else {
+        let t = t - 2.625f32 / d1;
+        n1 * t * t + 0.984375f32
+    }
+}
+
+fn cubic_bezier(x: f32, x1: f32, y1: f32, x2: f32, y2: f32) -> f32 {
+    fn sample(t: f32, p1: f32, p2: f32) -> f32 {
+        let u = 1f32 - t;
+        3f32 * u * u * t * p1 + 3f32 * u * t * t * p2 + t * t * t
+    }
+
+    fn sample_derivative(t: f32, p1: f32, p2: f32) -> f32 {
+        let u = 1f32 - t;
+        3f32 * u * u * p1 + 6f32 * u * t * (p2 - p1) + 3f32 * t * t * (1f32 - p2)
+    }
+
+    // Finds the curve parameter for x with Newton's method, falling back to bisection.
+    let mut t = x;
+
+    for _ in 0..8 {
+        let error = sample(t, x1, x2) - x;
+
+        if error.abs() < 1e-6f32 {
+            return sample(t, y1, y2);
+        }
+
+        let derivative = sample_derivative(t, x1, x2);
+
+        if derivative.abs() < 1e-6f32 {
+            break;
+        }
+
+        t -= error / derivative;
+    }
+
+    let mut lower = 0f32;
+    let mut upper = 1f32;
+    t = x;
+
+    for _ in 0..32 {
+        let sampled = sample(t, x1, x2);
+
+        if (sampled - x).abs() < 1e-6f32 {
+            break;
+        }
+
+        if sampled < x {
+            lower = t;
+        } else {
+            upper = t;
+        }
+
+        t = (lower + upper) * 0.5f32;
+    }
+
+    sample(t, y1, y2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn easings_start_at_zero_and_end_at_one() {
+        let easings = [
+            AnimationEasing::Linear,
+            AnimationEasing::Step(4),
+            AnimationEasing::QuadInOut,
+            AnimationEasing::QuintOut,
+            AnimationEasing::SineInOut,
+            AnimationEasing::ExpoInOut,
+            AnimationEasing::CircInOut,
+            AnimationEasing::BackInOut,
+            AnimationEasing::ElasticInOut,
+            AnimationEasing::BounceInOut,
+            AnimationEasing::CubicBezier(0.25, 0.1, 0.25, 1.0),
+        ];
+
+        for easing in easings {
+            assert!(easing.ease(0f32).abs() < 1e-4f32, "{:?}", easing);
+            assert!((easing.ease(1f32) - 1f32).abs() < 1e-4f32, "{:?}", easing);
+        }
+    }
+
+    #[test]
+    fn cubic_bezier_matches_linear_on_diagonal() {
+        let easing = AnimationEasing::CubicBezier(0.3, 0.3, 0.7, 0.7);
+
+        for index in 0..=10 {
+            let t = index as f32 / 10f32;
+            assert!((easing.ease(t) - t).abs() < 1e-4f32);
+        }
+    }
+}
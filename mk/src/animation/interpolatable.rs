@@ -1,3 +1,6 @@
+use crate::render::Color;
+use crate::structure::{Size, Vec2};
+
 pub trait Interpolatable {
     fn interpolate(lhs: Self, rhs: Self, t: f32) -> Self;
 }
@@ -5,12 +8,13 @@ pub trait Interpolatable {
 macro_rules! impl_interpolatable_numeric {
     ($ty:ty) => {
         impl Interpolatable for $ty {
+            // Extrapolates when t is out of [0, 1], so that overshooting easings are kept.
             fn interpolate(lhs: Self, rhs: Self, t: f32) -> Self {
-                if t <= 0f32 {
+                if t == 0f32 {
                     return lhs;
                 }
 
-                if t >= 1f32 {
+                if t == 1f32 {
                     return rhs;
                 }
 
@@ -86,3 +90,32 @@ impl Interpolatable for &str {
         rhs
     }
 }
+
+impl Interpolatable for Vec2 {
+    fn interpolate(lhs: Self, rhs: Self, t: f32) -> Self {
+        Self::new(
+            f32::interpolate(lhs.x, rhs.x, t),
+            f32::interpolate(lhs.y, rhs.y, t),
+        )
+    }
+}
+
+impl Interpolatable for Size {
+    fn interpolate(lhs: Self, rhs: Self, t: f32) -> Self {
+        Self::new(
+            f32::interpolate(lhs.width, rhs.width, t),
+            f32::interpolate(lhs.height, rhs.height, t),
+        )
+    }
+}
+
+impl Interpolatable for Color {
+    fn interpolate(lhs: Self, rhs: Self, t: f32) -> Self {
+        Self {
+            r: f32::interpolate(lhs.r, rhs.r, t),
+            g: f32::interpolate(lhs.g, rhs.g, t),
+            b: f32::interpolate(lhs.b, rhs.b, t),
+            a: f32::interpolate(lhs.a, rhs.a, t),
+        }
+    }
+}
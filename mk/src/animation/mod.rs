@@ -1,38 +1,107 @@
+mod color_space;
+mod easing;
 mod interpolatable;
+mod tween;
+mod tween_manager;
 
+pub use color_space::*;
+pub use easing::*;
 pub use interpolatable::*;
+pub use tween::*;
+pub use tween_manager::*;
 
-#[derive(Debug, Clone)]
+use crate::render::Color;
+use crate::structure::{Size, Vec2};
+use codegen::LuaRc;
+use mlua::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(LuaRc, Serialize, Deserialize, Debug, Clone)]
 pub struct Animation {
+    #[lua_hidden]
     pub time_lines: Vec<AnimationTimeLine>,
+    #[lua_hidden]
+    #[serde(default)]
+    pub events: Vec<AnimationEvent>,
     pub duration: f32,
+    #[serde(default)]
     pub looping: bool,
+    #[serde(default)]
     pub pingpong: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AnimationTimeLine {
     pub key_frames: Vec<AnimationKeyFrame>,
+    #[serde(default)]
     pub transform: Option<Vec<String>>,
     pub component: String,
     pub field: String,
 }
 
-#[derive(Debug, Clone)]
+// A named marker emitted to the entity when the playback crosses its normalized time.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AnimationEvent {
+    pub time: f32,
+    pub name: String,
+    #[serde(default)]
+    pub payload: Option<AnimationValue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AnimationKeyFrame {
     pub begin: f32,
     pub end: f32,
     pub from: AnimationValue,
     pub to: AnimationValue,
+    #[serde(default)]
     pub easing: AnimationEasing,
+    #[serde(default)]
+    pub color_space: AnimationColorSpace,
+}
+
+impl AnimationKeyFrame {
+    // Evaluates the value at the normalized time within this key frame, applying the easing.
+    pub fn sample(&self, normalized_time_in_key_frame: f32) -> AnimationValue {
+        let t = self.easing.ease(normalized_time_in_key_frame);
+
+        match (&self.from, &self.to) {
+            (AnimationValue::Integer(from), AnimationValue::Integer(to)) => {
+                AnimationValue::Integer(i64::interpolate(*from, *to, t))
+            }
+            (
+                AnimationValue::Integer(..) | AnimationValue::Float(..),
+                AnimationValue::Integer(..) | AnimationValue::Float(..),
+            ) => AnimationValue::Float(f64::interpolate(
+                self.from.as_float(),
+                self.to.as_float(),
+                t,
+            )),
+            (AnimationValue::Vec2(from), AnimationValue::Vec2(to)) => {
+                AnimationValue::Vec2(Vec2::interpolate(*from, *to, t))
+            }
+            (AnimationValue::Size(from), AnimationValue::Size(to)) => {
+                AnimationValue::Size(Size::interpolate(*from, *to, t))
+            }
+            (AnimationValue::Color(from), AnimationValue::Color(to)) => {
+                AnimationValue::Color(self.color_space.interpolate(*from, *to, t))
+            }
+            // Values that can't be blended take the destination, the same as `Interpolatable` for bool.
+            _ => self.to.clone(),
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
 pub enum AnimationValue {
     Bool(bool),
     Integer(i64),
     Float(f64),
     String(String),
+    Vec2(Vec2),
+    Size(Size),
+    Color(Color),
 }
 
 impl AnimationValue {
@@ -52,6 +121,7 @@ impl AnimationValue {
 
     pub fn as_float(&self) -> f64 {
         match self {
+            Self::Integer(value) => *value as f64,
             Self::Float(value) => *value,
             _ => f64::default(),
         }
@@ -63,9 +133,64 @@ impl AnimationValue {
             _ => "",
         }
     }
+
+    pub fn as_vec2(&self) -> Vec2 {
+        match self {
+            Self::Vec2(value) => *value,
+            _ => Vec2::default(),
+        }
+    }
+
+    pub fn as_size(&self) -> Size {
+        match self {
+            Self::Size(value) => *value,
+            _ => Size::default(),
+        }
+    }
+
+    pub fn as_color(&self) -> Color {
+        match self {
+            Self::Color(value) => *value,
+            _ => Color::new(),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum AnimationEasing {
-    Linear,
+impl<'lua> FromLua<'lua> for AnimationValue {
+    fn from_lua(value: LuaValue<'lua>, lua: &'lua Lua) -> LuaResult<Self> {
+        match value {
+            LuaValue::Boolean(value) => Ok(Self::Bool(value)),
+            LuaValue::Integer(value) => Ok(Self::Integer(value)),
+            LuaValue::Number(value) => Ok(Self::Float(value)),
+            LuaValue::String(..) => Ok(Self::String(String::from_lua(value, lua)?)),
+            LuaValue::Table(ref table) if table.contains_key("r")? => {
+                Ok(Self::Color(Color::from_lua(value, lua)?))
+            }
+            LuaValue::Table(ref table) if table.contains_key("width")? => {
+                Ok(Self::Size(Size::from_lua(value, lua)?))
+            }
+            LuaValue::Table(ref table) if table.contains_key("x")? => {
+                Ok(Self::Vec2(Vec2::from_lua(value, lua)?))
+            }
+            _ => Err(format!(
+                "the type {} must be a boolean, a number, a string, a color, a size or a vec2",
+                "AnimationValue"
+            )
+            .to_lua_err()),
+        }
+    }
+}
+
+impl<'lua> ToLua<'lua> for AnimationValue {
+    fn to_lua(self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        match self {
+            Self::Bool(value) => value.to_lua(lua),
+            Self::Integer(value) => value.to_lua(lua),
+            Self::Float(value) => value.to_lua(lua),
+            Self::String(value) => value.to_lua(lua),
+            Self::Vec2(value) => value.to_lua(lua),
+            Self::Size(value) => value.to_lua(lua),
+            Self::Color(value) => value.to_lua(lua),
+        }
+    }
 }
@@ -0,0 +1,72 @@
+use crate::animation::AnimationTimeLine;
+use legion::Entity;
+use mlua::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TweenId(pub u64);
+
+#[derive(Debug)]
+pub struct Tween {
+    pub entity: Entity,
+    // A time line with a single key frame spanning the whole tween.
+    pub time_line: AnimationTimeLine,
+    pub duration: f32,
+    pub delay: f32,
+    // The number of extra plays after the first one; `None` repeats forever.
+    pub repeat: Option<u32>,
+    // Plays every other repetition backwards.
+    pub pingpong: bool,
+    pub on_complete: Option<LuaRegistryKey>,
+    // Tweens chained to this one; they start once this one completes.
+    pub next: Vec<TweenId>,
+    pub is_pending: bool,
+    elapsed: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TweenStep {
+    // The uneased normalized time within the key frame.
+    pub normalized_time: f32,
+    pub finished: bool,
+}
+
+impl Tween {
+    pub fn new(entity: Entity, time_line: AnimationTimeLine, duration: f32) -> Self {
+        Self {
+            entity,
+            time_line,
+            duration,
+            delay: 0f32,
+            repeat: Some(0),
+            pingpong: false,
+            on_complete: None,
+            next: Vec::new(),
+            is_pending: false,
+            elapsed: 0f32,
+        }
+    }
+
+    // Returns `None` while the tween is waiting for its delay.
+    pub fn advance(&mut self, dt: f32) -> Option<TweenStep> {
+        self.elapsed += dt;
+
+        if self.elapsed < self.delay {
+            return None;
+        }
+
+        let progress = (self.elapsed - self.delay) / self.duration;
+        let (cycle, mut normalized_time, finished) = match self.repeat {
+            Some(repeat) if repeat as f32 + 1f32 <= progress => (repeat, 1f32, true),
+            _ => (progress as u32, progress.fract(), false),
+        };
+
+        if self.pingpong && cycle % 2 == 1 {
+            normalized_time = 1f32 - normalized_time;
+        }
+
+        Some(TweenStep {
+            normalized_time,
+            finished,
+        })
+    }
+}
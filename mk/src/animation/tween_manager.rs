@@ -0,0 +1,151 @@
+use crate::animation::{Tween, TweenId};
+use legion::Entity;
+use std::collections::BTreeMap;
+
+#[derive(Default, Debug)]
+pub struct TweenManager {
+    next_id: u64,
+    // Ordered by id, so the tweens are applied in the order they were created.
+    tweens: BTreeMap<TweenId, Tween>,
+}
+
+impl TweenManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Chained tweens wait for the given tween; they start right away if it is no longer alive.
+    pub fn add(&mut self, mut tween: Tween, after: Option<TweenId>) -> TweenId {
+        let id = TweenId(self.next_id);
+        self.next_id += 1;
+
+        if let Some(previous) = after.and_then(|after| self.tweens.get_mut(&after)) {
+            previous.next.push(id);
+            tween.is_pending = true;
+        }
+
+        self.tweens.insert(id, tween);
+        id
+    }
+
+    pub fn contains(&self, id: TweenId) -> bool {
+        self.tweens.contains_key(&id)
+    }
+
+    pub fn get(&self, id: TweenId) -> Option<&Tween> {
+        self.tweens.get(&id)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (TweenId, &mut Tween)> {
+        self.tweens.iter_mut().map(|(&id, tween)| (id, tween))
+    }
+
+    // Removes the tween along with every tween chained to it, returning the removed tweens so that
+    // their callbacks can be released.
+    pub fn cancel(&mut self, id: TweenId) -> Vec<Tween> {
+        let mut cancelled = Vec::new();
+        let mut ids = vec![id];
+
+        while let Some(id) = ids.pop() {
+            if let Some(mut tween) = self.tweens.remove(&id) {
+                ids.append(&mut tween.next);
+                cancelled.push(tween);
+            }
+        }
+
+        cancelled
+    }
+
+    pub fn cancel_entity(&mut self, entity: Entity) -> Vec<Tween> {
+        let ids = self
+            .tweens
+            .iter()
+            .filter(|(_, tween)| tween.entity == entity)
+            .map(|(&id, _)| id)
+            .collect::<Vec<_>>();
+
+        ids.into_iter().flat_map(|id| self.cancel(id)).collect()
+    }
+
+    // Removes the finished tween and starts the tweens chained to it.
+    pub fn complete(&mut self, id: TweenId) -> Option<Tween> {
+        let tween = self.tweens.remove(&id)?;
+
+        for next in &tween.next {
+            if let Some(next) = self.tweens.get_mut(next) {
+                next.is_pending = false;
+            }
+        }
+
+        Some(tween)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::{AnimationKeyFrame, AnimationTimeLine, AnimationValue};
+    use legion::World;
+
+    fn tween(entity: Entity) -> Tween {
+        let time_line = AnimationTimeLine {
+            key_frames: vec![AnimationKeyFrame {
+                begin: 0f32,
+                end: 1f32,
+                from: AnimationValue::Float(0f64),
+                to: AnimationValue::Float(1f64),
+                easing: Default::default(),
+                color_space: Default::default(),
+            }],
+            transform: None,
+            component: "camera".to_owned(),
+            field: "zoom".to_owned(),
+        };
+
+        Tween::new(entity, time_line, 1f32)
+    }
+
+    #[test]
+    fn chained_tweens_start_once_the_previous_completes() {
+        let entity = World::default().push(());
+        let mut manager = TweenManager::new();
+        let first = manager.add(tween(entity), None);
+        let second = manager.add(tween(entity), Some(first));
+        let third = manager.add(tween(entity), Some(second));
+
+        assert!(!manager.get(first).unwrap().is_pending);
+        assert!(manager.get(second).unwrap().is_pending);
+        assert!(manager.get(third).unwrap().is_pending);
+
+        assert!(manager.complete(first).is_some());
+        assert!(!manager.contains(first));
+        assert!(!manager.get(second).unwrap().is_pending);
+        assert!(manager.get(third).unwrap().is_pending);
+
+        // Chaining to a tween that is gone starts right away.
+        let late = manager.add(tween(entity), Some(first));
+        assert!(!manager.get(late).unwrap().is_pending);
+    }
+
+    #[test]
+    fn cancelling_removes_chained_tweens() {
+        let mut world = World::default();
+        let (entity, other_entity) = (world.push(()), world.push(()));
+        let mut manager = TweenManager::new();
+        let first = manager.add(tween(entity), None);
+        let second = manager.add(tween(entity), Some(first));
+        let third = manager.add(tween(entity), Some(second));
+        let other = manager.add(tween(other_entity), None);
+
+        assert_eq!(manager.cancel(second).len(), 2);
+        assert!(manager.contains(first));
+        assert!(!manager.contains(second));
+        assert!(!manager.contains(third));
+        assert!(manager.cancel(third).is_empty());
+
+        let chained = manager.add(tween(other_entity), Some(first));
+        assert_eq!(manager.cancel_entity(entity).len(), 2);
+        assert!(!manager.contains(chained));
+        assert!(manager.contains(other));
+    }
+}
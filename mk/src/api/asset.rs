@@ -1,11 +1,44 @@
+use crate::animation::{Animation, LuaRcAnimation};
 use crate::api::use_context;
 use crate::codegen_traits::LuaApiTable;
 use crate::render::{
     LuaRcFont, LuaRcShader, LuaRcSprite, LuaRcSpriteAtlas, LuaRcSpriteAtlasGrid,
-    LuaRcSpriteNinePatch,
+    LuaRcSpriteNinePatch, Shader, Sprite, SpriteAtlas, SpriteAtlasGrid, SpriteNinePatch,
 };
+use fontdue::Font;
 use mlua::prelude::*;
 
+pub struct AnimationAsset;
+
+impl LuaApiTable for AnimationAsset {
+    fn api_name() -> &'static str {
+        "Animation"
+    }
+
+    fn fill_api_table(lua: &Lua, table: &LuaTable) -> LuaResult<()> {
+        table.set(
+            "load",
+            lua.create_function(|_, name: String| {
+                let asset_mgr = use_context().asset_mgr();
+                let asset = asset_mgr.load(&name).map_err(|err| {
+                    format!("unable to load animation '{}' due to: {}", name, err).to_lua_err()
+                })?;
+                Ok(LuaRcAnimation::from(asset))
+            })?,
+        )?;
+        table.set(
+            "load_async",
+            lua.create_function(|_, name: String| {
+                let asset_mgr = use_context().asset_mgr();
+                asset_mgr.load_async::<Animation>(&name).map_err(|err| {
+                    format!("unable to load animation '{}' due to: {}", name, err).to_lua_err()
+                })
+            })?,
+        )?;
+        Ok(())
+    }
+}
+
 pub struct FontAsset;
 
 impl LuaApiTable for FontAsset {
@@ -24,6 +57,15 @@ impl LuaApiTable for FontAsset {
                 Ok(LuaRcFont::from(asset))
             })?,
         )?;
+        table.set(
+            "load_async",
+            lua.create_function(|_, name: String| {
+                let asset_mgr = use_context().asset_mgr();
+                asset_mgr.load_async::<Font>(&name).map_err(|err| {
+                    format!("unable to load font '{}' due to: {}", name, err).to_lua_err()
+                })
+            })?,
+        )?;
         Ok(())
     }
 }
@@ -46,6 +88,15 @@ impl LuaApiTable for ShaderAsset {
                 Ok(LuaRcShader::from(asset))
             })?,
         )?;
+        table.set(
+            "load_async",
+            lua.create_function(|_, name: String| {
+                let asset_mgr = use_context().asset_mgr();
+                asset_mgr.load_async::<Shader>(&name).map_err(|err| {
+                    format!("unable to load shader '{}' due to: {}", name, err).to_lua_err()
+                })
+            })?,
+        )?;
         Ok(())
     }
 }
@@ -68,6 +119,15 @@ impl LuaApiTable for SpriteAsset {
                 Ok(LuaRcSprite::from(asset))
             })?,
         )?;
+        table.set(
+            "load_async",
+            lua.create_function(|_, name: String| {
+                let asset_mgr = use_context().asset_mgr();
+                asset_mgr.load_async::<Sprite>(&name).map_err(|err| {
+                    format!("unable to load sprite '{}' due to: {}", name, err).to_lua_err()
+                })
+            })?,
+        )?;
         Ok(())
     }
 }
@@ -90,6 +150,15 @@ impl LuaApiTable for SpriteAtlasAsset {
                 Ok(LuaRcSpriteAtlas::from(asset))
             })?,
         )?;
+        table.set(
+            "load_async",
+            lua.create_function(|_, name: String| {
+                let asset_mgr = use_context().asset_mgr();
+                asset_mgr.load_async::<SpriteAtlas>(&name).map_err(|err| {
+                    format!("unable to load sprite atlas '{}' due to: {}", name, err).to_lua_err()
+                })
+            })?,
+        )?;
         Ok(())
     }
 }
@@ -116,6 +185,21 @@ impl LuaApiTable for SpriteAtlasGridAsset {
                 Ok(LuaRcSpriteAtlasGrid::from(asset))
             })?,
         )?;
+        table.set(
+            "load_async",
+            lua.create_function(|_, name: String| {
+                let asset_mgr = use_context().asset_mgr();
+                asset_mgr
+                    .load_async::<SpriteAtlasGrid>(&name)
+                    .map_err(|err| {
+                        format!(
+                            "unable to load sprite atlas grid '{}' due to: {}",
+                            name, err
+                        )
+                        .to_lua_err()
+                    })
+            })?,
+        )?;
         Ok(())
     }
 }
@@ -142,6 +226,21 @@ impl LuaApiTable for SpriteNinePatchAsset {
                 Ok(LuaRcSpriteNinePatch::from(asset))
             })?,
         )?;
+        table.set(
+            "load_async",
+            lua.create_function(|_, name: String| {
+                let asset_mgr = use_context().asset_mgr();
+                asset_mgr
+                    .load_async::<SpriteNinePatch>(&name)
+                    .map_err(|err| {
+                        format!(
+                            "unable to load sprite nine-patch '{}' due to: {}",
+                            name, err
+                        )
+                        .to_lua_err()
+                    })
+            })?,
+        )?;
         Ok(())
     }
 }
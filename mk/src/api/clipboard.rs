@@ -0,0 +1,26 @@
+use crate::api::use_context;
+use crate::codegen_traits::LuaApiTable;
+use mlua::prelude::*;
+
+pub struct ClipboardApi;
+
+impl LuaApiTable for ClipboardApi {
+    fn api_name() -> &'static str {
+        "Clipboard"
+    }
+
+    fn fill_api_table(lua: &Lua, table: &LuaTable) -> LuaResult<()> {
+        table.set(
+            "get_text",
+            lua.create_function(|_lua, ()| Ok(use_context().clipboard_mut().get_text()))?,
+        )?;
+        table.set(
+            "set_text",
+            lua.create_function(|_lua, text: String| {
+                use_context().clipboard_mut().set_text(text);
+                Ok(())
+            })?,
+        )?;
+        Ok(())
+    }
+}
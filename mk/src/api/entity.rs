@@ -1,19 +1,28 @@
+use crate::animation::LuaRcAnimation;
 use crate::api::use_context;
 use crate::codegen_traits::LuaApiTable;
 use crate::component::{
-    Camera, GlyphRenderer, GlyphRendererConfig, LuaComponentCamera, LuaComponentGlyphRenderer,
-    LuaComponentNinePatchRenderer, LuaComponentSpriteRenderer, LuaComponentTilemapRenderer,
-    LuaComponentUIScaler, NinePatchRenderer, Size, SpriteRenderer, TilemapRenderer, Transform,
-    UIElement, UIScaleMode, UIScaler,
+    validate_flipbook_speed, validate_zoom, AnimatorCondition, AnimatorParameter, AnimatorState,
+    AnimatorTransition, Camera, CameraViewport, FlipbookAnimator, FlipbookFrame, FlipbookMode,
+    FlipbookSource, FlipbookSprite, GlyphRenderer, GlyphRendererConfig, LuaComponentCamera,
+    LuaComponentFlipbookAnimator, LuaComponentGlyphRenderer, LuaComponentNinePatchRenderer,
+    LuaComponentSpriteRenderer, LuaComponentStateMachineAnimator, LuaComponentTilemapRenderer,
+    LuaComponentUIScaler, NinePatchRenderer, SingleAnimator, Size, SpriteRenderer,
+    StateMachineAnimator, TilemapRenderer, Transform, UIElement, UIScaleMode, UIScaler,
 };
+use crate::emit_diagnostic_error;
+use crate::event::events::{EntityDestroy, PerEntity};
+use crate::event::EntityEventManager;
 use crate::render::{
-    Color, LuaRcFont, LuaRcShader, LuaRcSprite, LuaRcSpriteNinePatch, LuaRcTilemap,
+    Color, LuaRcFont, LuaRcRenderTarget, LuaRcShader, LuaRcSprite, LuaRcSpriteAtlas,
+    LuaRcSpriteAtlasGrid, LuaRcSpriteNinePatch, LuaRcTilemap,
 };
 use crate::structure::Vec2;
-use crate::ui::{UIAnchor, UIMargin};
+use crate::ui::{FocusRequest, UIAnchor, UIMargin};
 use codegen::{LuaComponentNoWrapper, LuaStruct};
 use legion::world::Entry;
 use mlua::prelude::*;
+use std::collections::HashMap;
 use std::marker::PhantomData;
 
 #[derive(LuaComponentNoWrapper, Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -47,10 +56,22 @@ pub struct Entity {
     #[lua_readonly]
     #[lua_userfunc(get=lua_get_tilemap_renderer)]
     tilemap_renderer: PhantomData<LuaComponentTilemapRenderer>,
+    #[lua_readonly]
+    #[lua_userfunc(get=lua_get_flipbook_animator)]
+    flipbook_animator: PhantomData<LuaComponentFlipbookAnimator>,
+    #[lua_readonly]
+    #[lua_userfunc(get=lua_get_state_machine_animator)]
+    state_machine_animator: PhantomData<LuaComponentStateMachineAnimator>,
     #[lua_method]
     listen: PhantomData<()>,
     #[lua_method]
     unlisten: PhantomData<()>,
+    #[lua_method]
+    destroy: PhantomData<()>,
+    #[lua_method]
+    focus: PhantomData<()>,
+    #[lua_method]
+    blur: PhantomData<()>,
 }
 
 impl Entity {
@@ -66,8 +87,13 @@ impl Entity {
             sprite_renderer: PhantomData,
             nine_patch_renderer: PhantomData,
             tilemap_renderer: PhantomData,
+            flipbook_animator: PhantomData,
+            state_machine_animator: PhantomData,
             listen: PhantomData,
             unlisten: PhantomData,
+            destroy: PhantomData,
+            focus: PhantomData,
+            blur: PhantomData,
         }
     }
 
@@ -162,16 +188,108 @@ impl Entity {
         .to_lua(lua)
     }
 
+    fn lua_get_flipbook_animator<'lua>(&self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        self.with_entry(|e| {
+            e.get_component::<FlipbookAnimator>()
+                .ok()
+                .map(|_| LuaComponentFlipbookAnimator::from(self.entity))
+        })
+        .to_lua(lua)
+    }
+
+    fn lua_get_state_machine_animator<'lua>(&self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        self.with_entry(|e| {
+            e.get_component::<StateMachineAnimator>()
+                .ok()
+                .map(|_| LuaComponentStateMachineAnimator::from(self.entity))
+        })
+        .to_lua(lua)
+    }
+
     fn listen(&self, lua: &Lua, (event, function): (String, LuaFunction)) -> LuaResult<usize> {
-        use_context()
+        let entity = self.entity;
+        let hash = use_context()
             .entity_event_mgr_mut()
-            .add_entity_listener(lua, function, event, self.entity)
+            .add_entity_listener(lua, function, event, entity)?;
+        use_context().lua_mgr().track_listener(lua, hash, move || {
+            use_context()
+                .entity_event_mgr_mut()
+                .remove_entity_listener(entity, hash)
+        });
+        Ok(hash)
     }
 
     fn unlisten(&self, _lua: &Lua, handler: usize) -> LuaResult<()> {
-        use_context()
+        let context = use_context();
+        context
             .entity_event_mgr_mut()
             .remove_entity_listener(self.entity, handler);
+        context.lua_mgr().untrack_listener(handler);
+        Ok(())
+    }
+
+    fn destroy(&self, lua: &Lua, recursive: Option<bool>) -> LuaResult<()> {
+        let context = use_context();
+        let transform =
+            match self.with_entry(|e| e.get_component::<Transform>().ok().map(|t| t.index())) {
+                Some(transform) => transform,
+                None => return Ok(()),
+            };
+
+        let mut entities = vec![self.entity];
+
+        if let (Some(transform), true) = (transform, recursive.unwrap_or(false)) {
+            let transform_mgr = context.transform_mgr();
+            let mut indices = transform_mgr.children(transform).to_vec();
+
+            while let Some(index) = indices.pop() {
+                entities.push(transform_mgr.entity(index));
+                indices.extend_from_slice(transform_mgr.children(index));
+            }
+        }
+
+        // The entities are removed later by the destroy system, since the listener calling this may
+        // still be borrowing the event managers.
+        entities.retain(|entity| !context.destroy_queue_mut().contains(entity));
+        context.destroy_queue_mut().extend_from_slice(&entities);
+
+        for entity in entities {
+            if let Err(err) = EntityEventManager::emit(
+                lua,
+                &PerEntity {
+                    entity,
+                    event: "destroy".to_owned(),
+                    param: LuaMultiValue::new(),
+                },
+            ) {
+                emit_diagnostic_error!(format!(
+                    "an error occurred while emitting event '{}': {}",
+                    "destroy", err
+                ));
+            }
+
+            context
+                .event_mgr()
+                .dispatcher()
+                .emit(lua, &EntityDestroy { entity });
+        }
+
+        Ok(())
+    }
+
+    // Text input is sent to the focused entity, which changes once the current event has been
+    // dispatched.
+    fn focus(&self, _lua: &Lua, _: ()) -> LuaResult<()> {
+        use_context()
+            .focus_requests_mut()
+            .push(FocusRequest::Focus(self.entity));
+        Ok(())
+    }
+
+    fn blur(&self, _lua: &Lua, _: ()) -> LuaResult<()> {
+        use_context()
+            .focus_requests_mut()
+            .push(FocusRequest::Blur(self.entity));
         Ok(())
     }
 }
@@ -260,10 +378,30 @@ impl LuaApiTable for Entity {
                 }
 
                 if let Some(param) = param.camera {
-                    entry.add_component(Camera {
-                        layer: param.layer.unwrap_or_default(),
-                        order: param.order.unwrap_or_default(),
-                    });
+                    let mut camera = Camera::new(
+                        param.layer.unwrap_or_default(),
+                        param.order.unwrap_or_default(),
+                    );
+
+                    if let Some(viewport) = param.viewport {
+                        camera.viewport = viewport;
+                    }
+
+                    if let Some(zoom) = param.zoom {
+                        camera.zoom = validate_zoom(zoom)?;
+                    }
+
+                    camera.target = param.target.map(<_>::from);
+                    // Targets keep their contents between frames, so they're cleared unless asked
+                    // otherwise.
+                    camera.clear_color = match param.clear_color {
+                        Some(color) => Some(color),
+                        None if camera.target.is_some() => Some(Color::new()),
+                        None => None,
+                    };
+                    camera.clear_depth = param.clear_depth.unwrap_or_default();
+
+                    entry.add_component(camera);
                 }
 
                 if let Some(param) = param.glyph_renderer {
@@ -357,6 +495,160 @@ impl LuaApiTable for Entity {
                     entry.add_component(tilemap_renderer);
                 }
 
+                if let Some(param) = param.flipbook_animator {
+                    let source = match (param.grid, param.atlas) {
+                        (Some(grid), None) => FlipbookSource::Grid(<_>::from(grid)),
+                        (None, Some(atlas)) => FlipbookSource::Atlas(<_>::from(atlas)),
+                        _ => {
+                            return Err("flipbook animator requires either a grid or an atlas"
+                                .to_lua_err())
+                        }
+                    };
+                    let frames = param
+                        .frames
+                        .into_iter()
+                        .map(|frame| FlipbookFrame {
+                            sprite: frame.sprite,
+                            duration: frame.duration,
+                        })
+                        .collect::<Vec<_>>();
+
+                    for frame in &frames {
+                        if source.sprite(&frame.sprite).is_none() {
+                            return Err(format!(
+                                "flipbook frame refers to an unknown sprite {:?}",
+                                frame.sprite
+                            )
+                            .to_lua_err());
+                        }
+
+                        if frame.duration <= 0f32 {
+                            return Err("flipbook frame's duration must be greater than zero"
+                                .to_lua_err());
+                        }
+                    }
+
+                    let mut flipbook_animator = FlipbookAnimator::new(
+                        source,
+                        frames,
+                        param.mode.unwrap_or(FlipbookMode::Loop),
+                    );
+
+                    if let Some(speed) = param.speed {
+                        flipbook_animator.speed = validate_flipbook_speed(speed)?;
+                    }
+
+                    if let (Ok(sprite_renderer), Some(sprite)) = (
+                        entry.get_component_mut::<SpriteRenderer>(),
+                        flipbook_animator.sprite(),
+                    ) {
+                        sprite_renderer.sprite = sprite.clone();
+                    }
+
+                    entry.add_component(flipbook_animator);
+                }
+
+                if let Some(param) = param.single_animator {
+                    entry.add_component(SingleAnimator::new(
+                        <_>::from(param.animation),
+                        param.speed.unwrap_or(1f32),
+                    ));
+                }
+
+                if let Some(param) = param.state_machine_animator {
+                    let states = param
+                        .states
+                        .into_iter()
+                        .map(|(name, state)| {
+                            (
+                                name,
+                                AnimatorState {
+                                    animation: <_>::from(state.animation),
+                                    speed: state.speed.unwrap_or(1f32),
+                                },
+                            )
+                        })
+                        .collect::<HashMap<_, _>>();
+                    let parameters = param.parameters.unwrap_or_default();
+                    let transitions = param
+                        .transitions
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|transition| AnimatorTransition {
+                            from: transition.from,
+                            to: transition.to,
+                            conditions: transition.conditions.unwrap_or_default(),
+                            duration: transition.duration.unwrap_or(0f32),
+                            exit_time: transition.exit_time,
+                        })
+                        .collect::<Vec<_>>();
+
+                    if !states.contains_key(&param.state) {
+                        return Err(format!(
+                            "state machine animator's initial state '{}' does not exist",
+                            param.state
+                        )
+                        .to_lua_err());
+                    }
+
+                    for transition in &transitions {
+                        for state in transition.from.iter().chain(Some(&transition.to)) {
+                            if !states.contains_key(state) {
+                                return Err(format!(
+                                    "state machine animator's transition refers to an unknown state '{}'",
+                                    state
+                                )
+                                .to_lua_err());
+                            }
+                        }
+
+                        if transition.conditions.is_empty() && transition.exit_time.is_none() {
+                            return Err(format!(
+                                "state machine animator's transition to '{}' requires conditions or an exit time",
+                                transition.to
+                            )
+                            .to_lua_err());
+                        }
+
+                        if transition.duration < 0f32 {
+                            return Err(format!(
+                                "state machine animator's transition to '{}' has a negative duration",
+                                transition.to
+                            )
+                            .to_lua_err());
+                        }
+
+                        for condition in &transition.conditions {
+                            match parameters.get(&condition.parameter) {
+                                Some(&parameter) if condition.is_compatible(parameter) => {}
+                                Some(parameter) => {
+                                    return Err(format!(
+                                        "state machine animator's condition {:?} can't be evaluated against the parameter {:?}",
+                                        condition, parameter
+                                    )
+                                    .to_lua_err())
+                                }
+                                None => {
+                                    return Err(format!(
+                                        "state machine animator's condition refers to an unknown parameter '{}'",
+                                        condition.parameter
+                                    )
+                                    .to_lua_err())
+                                }
+                            }
+                        }
+                    }
+
+                    let mut state_machine_animator =
+                        StateMachineAnimator::new(states, transitions, parameters, param.state);
+
+                    if let Some(speed) = param.speed {
+                        state_machine_animator.speed = speed;
+                    }
+
+                    entry.add_component(state_machine_animator);
+                }
+
                 Ok(Entity::new(entity))
             })?,
         )?;
@@ -410,6 +702,11 @@ struct UIScalerBuildParam {
 struct CameraBuildParam {
     pub layer: Option<crate::render::Layer>,
     pub order: Option<isize>,
+    pub viewport: Option<CameraViewport>,
+    pub zoom: Option<f32>,
+    pub clear_color: Option<Color>,
+    pub clear_depth: Option<bool>,
+    pub target: Option<LuaRcRenderTarget>,
 }
 
 #[derive(LuaStruct)]
@@ -453,6 +750,51 @@ struct TilemapRendererBuildParam {
     pub tilemap: LuaRcTilemap,
 }
 
+#[derive(LuaStruct)]
+struct FlipbookFrameBuildParam {
+    pub sprite: FlipbookSprite,
+    pub duration: f32,
+}
+
+#[derive(LuaStruct)]
+struct FlipbookAnimatorBuildParam {
+    pub grid: Option<LuaRcSpriteAtlasGrid>,
+    pub atlas: Option<LuaRcSpriteAtlas>,
+    pub frames: Vec<FlipbookFrameBuildParam>,
+    pub mode: Option<FlipbookMode>,
+    pub speed: Option<f32>,
+}
+
+#[derive(LuaStruct)]
+struct SingleAnimatorBuildParam {
+    pub animation: LuaRcAnimation,
+    pub speed: Option<f32>,
+}
+
+#[derive(LuaStruct)]
+struct AnimatorStateBuildParam {
+    pub animation: LuaRcAnimation,
+    pub speed: Option<f32>,
+}
+
+#[derive(LuaStruct)]
+struct AnimatorTransitionBuildParam {
+    pub from: Option<String>,
+    pub to: String,
+    pub conditions: Option<Vec<AnimatorCondition>>,
+    pub duration: Option<f32>,
+    pub exit_time: Option<f32>,
+}
+
+#[derive(LuaStruct)]
+struct StateMachineAnimatorBuildParam {
+    pub states: HashMap<String, AnimatorStateBuildParam>,
+    pub state: String,
+    pub parameters: Option<HashMap<String, AnimatorParameter>>,
+    pub transitions: Option<Vec<AnimatorTransitionBuildParam>>,
+    pub speed: Option<f32>,
+}
+
 #[derive(LuaStruct)]
 struct EntityBuildParam {
     name: Option<String>,
@@ -465,4 +807,7 @@ struct EntityBuildParam {
     sprite_renderer: Option<SpriteRendererBuildParam>,
     nine_patch_renderer: Option<NinePatchRendererBuildParam>,
     tilemap_renderer: Option<TilemapRendererBuildParam>,
+    flipbook_animator: Option<FlipbookAnimatorBuildParam>,
+    single_animator: Option<SingleAnimatorBuildParam>,
+    state_machine_animator: Option<StateMachineAnimatorBuildParam>,
 }
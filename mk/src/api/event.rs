@@ -1,7 +1,9 @@
 use crate::codegen_traits::LuaApiTable;
 use crate::event::events::{
-    Diagnostic, KeyDown, KeyUp, PointerDown, PointerEnter, PointerExit, PointerMove, PointerUp,
-    PostRender, PostUpdate, PreRender, PreUpdate, Update,
+    AssetLoaded, AssetReloaded, Diagnostic, EntityDestroy, GamepadButtonDown, GamepadButtonUp,
+    GamepadConnected, GamepadDisconnected, KeyDown, KeyUp, PointerDown, PointerEnter, PointerExit,
+    PointerMove, PointerScroll, PointerUp, PostRender, PostUpdate, PreRender, PreUpdate, TextInput,
+    Update,
 };
 use mlua::prelude::*;
 
@@ -22,6 +24,9 @@ impl LuaApiTable for Event {
         register_api_table::<PostUpdate>(lua, &table)?;
         register_api_table::<PreRender>(lua, &table)?;
         register_api_table::<PostRender>(lua, &table)?;
+        register_api_table::<EntityDestroy>(lua, &table)?;
+        register_api_table::<AssetLoaded>(lua, &table)?;
+        register_api_table::<AssetReloaded>(lua, &table)?;
         register_api_table::<KeyDown>(lua, &table)?;
         register_api_table::<KeyUp>(lua, &table)?;
         register_api_table::<PointerEnter>(lua, &table)?;
@@ -29,6 +34,12 @@ impl LuaApiTable for Event {
         register_api_table::<PointerMove>(lua, &table)?;
         register_api_table::<PointerDown>(lua, &table)?;
         register_api_table::<PointerUp>(lua, &table)?;
+        register_api_table::<PointerScroll>(lua, &table)?;
+        register_api_table::<GamepadConnected>(lua, &table)?;
+        register_api_table::<GamepadDisconnected>(lua, &table)?;
+        register_api_table::<GamepadButtonDown>(lua, &table)?;
+        register_api_table::<GamepadButtonUp>(lua, &table)?;
+        register_api_table::<TextInput>(lua, &table)?;
         Ok(())
     }
 }
@@ -0,0 +1,164 @@
+use crate::api::use_context;
+use crate::codegen_traits::LuaApiTable;
+use crate::input::{
+    AxisBinding, GamepadAxis, GamepadButton, GamepadId, GamepadStick, InputBindings, InputSource,
+};
+use mlua::prelude::*;
+
+pub struct InputApi;
+
+impl LuaApiTable for InputApi {
+    fn api_name() -> &'static str {
+        "Input"
+    }
+
+    fn fill_api_table(lua: &Lua, table: &LuaTable) -> LuaResult<()> {
+        table.set(
+            "define_action",
+            lua.create_function(|_lua, (name, sources): (String, Vec<InputSource>)| {
+                use_context().input_mgr_mut().define_action(name, sources);
+                Ok(())
+            })?,
+        )?;
+        table.set(
+            "define_axis",
+            lua.create_function(|_lua, (name, binding): (String, AxisBinding)| {
+                use_context().input_mgr_mut().define_axis(name, binding);
+                Ok(())
+            })?,
+        )?;
+        table.set(
+            "remove_action",
+            lua.create_function(|_lua, name: String| {
+                use_context().input_mgr_mut().remove_action(&name);
+                Ok(())
+            })?,
+        )?;
+        table.set(
+            "remove_axis",
+            lua.create_function(|_lua, name: String| {
+                use_context().input_mgr_mut().remove_axis(&name);
+                Ok(())
+            })?,
+        )?;
+        table.set(
+            "load_bindings",
+            lua.create_function(|_lua, path: String| {
+                let context = use_context();
+                let bindings = context
+                    .asset_mgr()
+                    .source()
+                    .read_to_string(path.as_ref())
+                    .map_err(|err| err.to_string())
+                    .and_then(|json| {
+                        serde_json::from_str::<InputBindings>(&json).map_err(|err| err.to_string())
+                    })
+                    .map_err(|err| {
+                        format!("unable to load input bindings '{}' due to: {}", path, err)
+                            .to_lua_err()
+                    })?;
+                context.input_mgr_mut().apply_bindings(bindings);
+                Ok(())
+            })?,
+        )?;
+        table.set(
+            "is_key_down",
+            lua.create_function(|_lua, source: InputSource| {
+                Ok(use_context().input_mgr().is_source_down(source))
+            })?,
+        )?;
+        table.set(
+            "is_down",
+            lua.create_function(|_lua, action: String| {
+                use_context()
+                    .input_mgr()
+                    .is_down(&action)
+                    .ok_or_else(|| undefined_action(&action))
+            })?,
+        )?;
+        table.set(
+            "just_pressed",
+            lua.create_function(|_lua, action: String| {
+                use_context()
+                    .input_mgr()
+                    .just_pressed(&action)
+                    .ok_or_else(|| undefined_action(&action))
+            })?,
+        )?;
+        table.set(
+            "just_released",
+            lua.create_function(|_lua, action: String| {
+                use_context()
+                    .input_mgr()
+                    .just_released(&action)
+                    .ok_or_else(|| undefined_action(&action))
+            })?,
+        )?;
+        table.set(
+            "axis_value",
+            lua.create_function(|_lua, axis: String| {
+                use_context()
+                    .input_mgr()
+                    .axis_value(&axis)
+                    .ok_or_else(|| format!("input axis '{}' is not defined", axis).to_lua_err())
+            })?,
+        )?;
+        table.set(
+            "dead_zone",
+            lua.create_function(|_lua, ()| Ok(use_context().input_mgr().dead_zone()))?,
+        )?;
+        table.set(
+            "set_dead_zone",
+            lua.create_function(|_lua, dead_zone: f32| {
+                if !(0f32..1f32).contains(&dead_zone) {
+                    return Err("dead zone must be in range [0, 1)".to_lua_err());
+                }
+
+                use_context().input_mgr_mut().set_dead_zone(dead_zone);
+                Ok(())
+            })?,
+        )?;
+        table.set(
+            "gamepads",
+            lua.create_function(|_lua, ()| {
+                Ok(use_context()
+                    .input_mgr()
+                    .gamepads()
+                    .map(|(id, _)| id)
+                    .collect::<Vec<_>>())
+            })?,
+        )?;
+        table.set(
+            "gamepad_name",
+            lua.create_function(|_lua, id: GamepadId| {
+                Ok(use_context()
+                    .input_mgr()
+                    .gamepad(id)
+                    .map(|gamepad| gamepad.name.clone()))
+            })?,
+        )?;
+        table.set(
+            "is_gamepad_button_down",
+            lua.create_function(|_lua, (id, button): (GamepadId, GamepadButton)| {
+                Ok(use_context().input_mgr().is_gamepad_button_down(id, button))
+            })?,
+        )?;
+        table.set(
+            "gamepad_axis",
+            lua.create_function(|_lua, (id, axis): (GamepadId, GamepadAxis)| {
+                Ok(use_context().input_mgr().gamepad_axis(id, axis))
+            })?,
+        )?;
+        table.set(
+            "gamepad_stick",
+            lua.create_function(|_lua, (id, stick): (GamepadId, GamepadStick)| {
+                Ok(use_context().input_mgr().gamepad_stick(id, stick))
+            })?,
+        )?;
+        Ok(())
+    }
+}
+
+fn undefined_action(action: &str) -> LuaError {
+    format!("input action '{}' is not defined", action).to_lua_err()
+}
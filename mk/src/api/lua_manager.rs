@@ -1,24 +1,91 @@
 use crate::api::lua_api;
-use crate::EngineContextWithoutSystemManager;
+use crate::{emit_diagnostic_error, emit_diagnostic_info, EngineContextWithoutSystemManager};
 use mlua::prelude::*;
-use std::path::Path;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fs::{metadata as fs_metadata, read_to_string};
+use std::mem::take;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
 static mut CONTEXT: Option<Arc<EngineContextWithoutSystemManager>> = None;
 
+const HOT_RELOAD_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LuaScriptPath {
+    File(PathBuf),
+    Asset(PathBuf),
+}
+
+impl LuaScriptPath {
+    pub fn chunk_name(&self) -> String {
+        match self {
+            Self::File(path) => format!("@{}", path.as_os_str().to_str().unwrap_or("unknown")),
+            Self::Asset(path) => format!("@{}", path.display()),
+        }
+    }
+
+    pub fn read(&self) -> LuaResult<String> {
+        match self {
+            Self::File(path) => read_to_string(path).map_err(|err| err.to_lua_err()),
+            Self::Asset(path) => use_context()
+                .asset_mgr()
+                .source()
+                .read_to_string(path)
+                .map_err(|err| err.to_string().to_lua_err()),
+        }
+    }
+
+    pub fn modified(&self) -> Option<SystemTime> {
+        match self {
+            Self::File(path) => fs_metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .ok(),
+            Self::Asset(path) => use_context().asset_mgr().source().modified(path),
+        }
+    }
+}
+
+struct LuaModule {
+    path: LuaScriptPath,
+    modified: Option<SystemTime>,
+    // The listeners bound by the module, keyed by their handler.
+    unbinders: Vec<(usize, Box<dyn FnOnce()>)>,
+}
+
 pub struct LuaManager {
     lua: Lua,
+    hot_reload: Cell<bool>,
+    last_hot_reload: Cell<Instant>,
+    modules: RefCell<HashMap<String, LuaModule>>,
+    module_stack: RefCell<Vec<String>>,
 }
 
 impl LuaManager {
     pub fn new() -> Self {
-        Self { lua: Lua::new() }
+        Self {
+            lua: Lua::new(),
+            hot_reload: Cell::new(false),
+            last_hot_reload: Cell::new(Instant::now()),
+            modules: RefCell::new(HashMap::new()),
+            module_stack: RefCell::new(Vec::new()),
+        }
     }
 
     pub fn lua(&self) -> &Lua {
         &self.lua
     }
 
+    pub fn hot_reload(&self) -> bool {
+        self.hot_reload.get()
+    }
+
+    pub fn set_hot_reload(&self, hot_reload: bool) {
+        self.hot_reload.set(hot_reload);
+    }
+
     pub fn init_lua<'lua, S: AsRef<str>>(
         &'lua self,
         context: Arc<EngineContextWithoutSystemManager>,
@@ -30,6 +97,14 @@ impl LuaManager {
             .globals()
             .set(api_prefix.as_ref(), lua_api(&self.lua)?)?;
 
+        // Modules are searched in the asset source right after the preloaded ones.
+        let searchers: LuaTable = self
+            .lua
+            .globals()
+            .get::<_, LuaTable>("package")?
+            .get("searchers")?;
+        searchers.raw_insert(2, self.lua.create_function(search_module)?)?;
+
         Ok(())
     }
 
@@ -50,6 +125,132 @@ impl LuaManager {
             .exec()
     }
 
+    // Executes the script as a module, so that it can be reloaded along with the listeners it has bound.
+    // The entities it has built are kept as they are.
+    pub fn execute_module<'lua>(
+        &'lua self,
+        name: &str,
+        path: LuaScriptPath,
+    ) -> LuaResult<LuaValue<'lua>> {
+        let chunk_name = path.chunk_name();
+        let modified = path.modified();
+        let script = path.read();
+
+        // Record the module first, so that it is still watched even if it fails to compile.
+        self.modules
+            .borrow_mut()
+            .entry(name.to_owned())
+            .and_modify(|module| {
+                module.path = path.clone();
+                module.modified = modified;
+            })
+            .or_insert_with(|| LuaModule {
+                path: path.clone(),
+                modified,
+                unbinders: Vec::new(),
+            });
+
+        let function = self
+            .lua
+            .load(&script?)
+            .set_name(chunk_name.as_str())?
+            .into_function()?;
+        let unbinders = match self.modules.borrow_mut().get_mut(name) {
+            Some(module) => take(&mut module.unbinders),
+            None => Vec::new(),
+        };
+
+        for (_, unbind) in unbinders {
+            unbind();
+        }
+
+        self.module_stack.borrow_mut().push(name.to_owned());
+        let result = function.call::<_, LuaValue>(name);
+        self.module_stack.borrow_mut().pop();
+
+        result
+    }
+
+    // Attributes the listener to the module that defines the calling function, or to the module being executed.
+    pub fn track_listener(&self, lua: &Lua, handler: usize, unbind: impl 'static + FnOnce()) {
+        let caller = lua
+            .inspect_stack(1)
+            .and_then(|debug| debug.source().source.map(|source| source.to_vec()));
+        let mut modules = self.modules.borrow_mut();
+        let name = caller
+            .and_then(|caller| {
+                modules
+                    .iter()
+                    .find(|(_, module)| module.path.chunk_name().as_bytes() == caller.as_slice())
+                    .map(|(name, _)| name.clone())
+            })
+            .or_else(|| self.module_stack.borrow().last().cloned());
+
+        if let Some(module) = name.and_then(|name| modules.get_mut(&name)) {
+            module.unbinders.push((handler, Box::new(unbind)));
+        }
+    }
+
+    pub fn untrack_listener(&self, handler: usize) {
+        for module in self.modules.borrow_mut().values_mut() {
+            module
+                .unbinders
+                .retain(|(tracked_handler, _)| *tracked_handler != handler);
+        }
+    }
+
+    // Re-executes every module whose script has been modified.
+    pub fn poll_hot_reload(&self) {
+        if !self.hot_reload.get() || self.last_hot_reload.get().elapsed() < HOT_RELOAD_INTERVAL {
+            return;
+        }
+
+        self.last_hot_reload.set(Instant::now());
+
+        let modified = self
+            .modules
+            .borrow()
+            .iter()
+            .filter(|(_, module)| module.path.modified() != module.modified)
+            .map(|(name, module)| (name.clone(), module.path.clone()))
+            .collect::<Vec<_>>();
+
+        for (name, path) in modified {
+            match self.reload_module(&name, path) {
+                Ok(()) => {
+                    emit_diagnostic_info!(format!("lua module '{}' has been reloaded.", name));
+                }
+                Err(err) => {
+                    emit_diagnostic_error!(format!(
+                        "unable to reload lua module '{}' due to: {}",
+                        name, err
+                    ));
+                }
+            }
+        }
+    }
+
+    fn reload_module(&self, name: &str, path: LuaScriptPath) -> LuaResult<()> {
+        let value = self.execute_module(name, path)?;
+        let loaded: LuaTable = self
+            .lua
+            .globals()
+            .get::<_, LuaTable>("package")?
+            .get("loaded")?;
+
+        // Keep `require` returning the latest version of the module.
+        if loaded.contains_key(name)? {
+            loaded.set(
+                name,
+                match value {
+                    LuaValue::Nil => LuaValue::Boolean(true),
+                    value => value,
+                },
+            )?;
+        }
+
+        Ok(())
+    }
     // pub fn execute_coroutine(
     //     &self,
     //     function: LuaFunction,
@@ -84,6 +285,33 @@ impl LuaManager {
     // }
 }
 
+fn search_module<'lua>(lua: &'lua Lua, name: String) -> LuaResult<LuaMultiValue<'lua>> {
+    let base = Path::new("scripts").join(name.replace('.', "/"));
+    let candidates = [base.with_extension("lua"), base.join("init.lua")];
+
+    match candidates
+        .iter()
+        .find(|path| use_context().asset_mgr().source().exists(path))
+    {
+        Some(path) => {
+            let module_path = path.clone();
+            let loader = lua.create_function(move |_lua, (name, _): (String, LuaValue)| {
+                use_context()
+                    .lua_mgr()
+                    .execute_module(&name, LuaScriptPath::Asset(module_path.clone()))
+            })?;
+
+            (loader, path.display().to_string()).to_lua_multi(lua)
+        }
+        None => format!(
+            "\n\tno asset '{}'\n\tno asset '{}'",
+            candidates[0].display(),
+            candidates[1].display()
+        )
+        .to_lua_multi(lua),
+    }
+}
+
 // TODO: Move this to outer scope.
 pub fn use_context() -> &'static Arc<EngineContextWithoutSystemManager> {
     return unsafe { &CONTEXT }.as_ref().unwrap();
@@ -1,18 +1,26 @@
 mod asset;
+mod clipboard;
 mod coroutine;
 mod entity;
 mod event;
+mod input;
 mod lua_manager;
+mod render_target;
 mod screen;
 mod time;
+mod tween;
 
 pub use asset::*;
+pub use clipboard::*;
 pub use coroutine::*;
 pub use entity::*;
 pub use event::*;
+pub use input::*;
 pub use lua_manager::*;
+pub use render_target::*;
 pub use screen::*;
 pub use time::*;
+pub use tween::*;
 
 use crate::codegen_traits::LuaApiTable;
 use crate::component::Transform;
@@ -88,10 +96,14 @@ where
 pub fn lua_api(lua: &Lua) -> LuaResult<LuaTable> {
     let table = lua.create_table()?;
 
+    register_api_table::<AnimationAsset>(lua, &table)?;
+    register_api_table::<ClipboardApi>(lua, &table)?;
     register_api_table::<Color>(lua, &table)?;
     register_api_table::<Entity>(lua, &table)?;
     register_api_table::<Event>(lua, &table)?;
     register_api_table::<FontAsset>(lua, &table)?;
+    register_api_table::<InputApi>(lua, &table)?;
+    register_api_table::<RenderTargetApi>(lua, &table)?;
     register_api_table::<Screen>(lua, &table)?;
     register_api_table::<ShaderAsset>(lua, &table)?;
     register_api_table::<SpriteAsset>(lua, &table)?;
@@ -100,6 +112,7 @@ pub fn lua_api(lua: &Lua) -> LuaResult<LuaTable> {
     register_api_table::<SpriteNinePatchAsset>(lua, &table)?;
     register_api_table::<Time>(lua, &table)?;
     register_api_table::<Transform>(lua, &table)?;
+    register_api_table::<TweenApi>(lua, &table)?;
     register_api_table::<Vec2>(lua, &table)?;
     // table.set("Coroutine", lua_api_coroutine(lua)?)?;
 
@@ -0,0 +1,52 @@
+use crate::codegen_traits::LuaApiTable;
+use crate::render::{LuaRcRenderTarget, LuaRcSprite, RenderTarget, Sprite, TexelMapping};
+use mlua::prelude::*;
+use std::sync::Arc;
+
+pub struct RenderTargetApi;
+
+impl LuaApiTable for RenderTargetApi {
+    fn api_name() -> &'static str {
+        "RenderTarget"
+    }
+
+    fn fill_api_table(lua: &Lua, table: &LuaTable) -> LuaResult<()> {
+        table.set(
+            "create",
+            lua.create_function(|_, (width, height): (u32, u32)| {
+                if width == 0 || height == 0 {
+                    return Err(format!(
+                        "the size of the render target must not be zero, but {}x{} was given",
+                        width, height
+                    )
+                    .to_lua_err());
+                }
+
+                let render_target =
+                    RenderTarget::with_size_rgba_u8(width, height).map_err(|err| {
+                        format!("unable to create render target due to: {}", err).to_lua_err()
+                    })?;
+                Ok(LuaRcRenderTarget::from(Arc::new(render_target)))
+            })?,
+        )?;
+        table.set(
+            "sprite",
+            lua.create_function(|_, render_target: LuaRcRenderTarget| {
+                // The sprite shares the texture, so it always shows what's rendered most recently.
+                let render_target = render_target.0;
+                let sprite = Sprite::from_atlas(
+                    render_target.texture().clone(),
+                    TexelMapping::new((0, 0), (render_target.width(), render_target.height())),
+                );
+                Ok(LuaRcSprite::from(Arc::new(sprite)))
+            })?,
+        )?;
+        table.set(
+            "size",
+            lua.create_function(|_, render_target: LuaRcRenderTarget| {
+                Ok((render_target.0.width(), render_target.0.height()))
+            })?,
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use crate::animation::{
+    AnimationColorSpace, AnimationEasing, AnimationKeyFrame, AnimationTimeLine, AnimationValue,
+    Tween, TweenId,
+};
+use crate::api::{use_context, Entity};
+use crate::codegen_traits::LuaApiTable;
+use mlua::prelude::*;
+use std::any::type_name;
+
+pub struct TweenApi;
+
+impl LuaApiTable for TweenApi {
+    fn api_name() -> &'static str {
+        "Tween"
+    }
+
+    fn fill_api_table(lua: &Lua, table: &LuaTable) -> LuaResult<()> {
+        table.set(
+            "play",
+            lua.create_function(|lua, (entity, param): (Entity, LuaTable)| {
+                let tween = build_tween(lua, entity, param)?;
+                Ok(use_context().tween_mgr_mut().add(tween, None))
+            })?,
+        )?;
+        table.set(
+            "cancel",
+            lua.create_function(|lua, entity: Entity| {
+                let cancelled = use_context().tween_mgr_mut().cancel_entity(entity.entity());
+                release_callbacks(lua, cancelled);
+                Ok(())
+            })?,
+        )?;
+        Ok(())
+    }
+}
+
+impl LuaUserData for TweenId {
+    fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_meta_method(LuaMetaMethod::Eq, |_lua, this, other: LuaAnyUserData| {
+            Ok(match other.borrow::<Self>() {
+                Ok(other) => *this == *other,
+                Err(..) => false,
+            })
+        });
+        methods.add_meta_method(LuaMetaMethod::Index, |lua, this, value: String| {
+            let tween_mgr = use_context().tween_mgr();
+            Ok(match value.as_str() {
+                "id" => this.0.to_lua(lua)?,
+                "is_active" => tween_mgr.contains(*this).to_lua(lua)?,
+                "is_pending" => tween_mgr
+                    .get(*this)
+                    .is_some_and(|tween| tween.is_pending)
+                    .to_lua(lua)?,
+                _ => {
+                    return Err(format!(
+                        "property '{}' is not exists on the '{}'",
+                        value,
+                        type_name::<Self>()
+                    )
+                    .to_lua_err())
+                }
+            })
+        });
+        methods.add_method("cancel", |lua, this, ()| {
+            let cancelled = use_context().tween_mgr_mut().cancel(*this);
+            let is_cancelled = !cancelled.is_empty();
+            release_callbacks(lua, cancelled);
+            Ok(is_cancelled)
+        });
+        methods.add_method("chain", |lua, this, (entity, param): (Entity, LuaTable)| {
+            let tween = build_tween(lua, entity, param)?;
+            Ok(use_context().tween_mgr_mut().add(tween, Some(*this)))
+        });
+    }
+}
+
+fn build_tween<'lua>(lua: &'lua Lua, entity: Entity, param: LuaTable<'lua>) -> LuaResult<Tween> {
+    let duration = param.get::<_, f32>("duration")?;
+    let delay = param.get::<_, Option<f32>>("delay")?.unwrap_or(0f32);
+
+    if duration <= 0f32 {
+        return Err("tween's duration must be greater than zero".to_lua_err());
+    }
+
+    if delay < 0f32 {
+        return Err("tween's delay must not be negative".to_lua_err());
+    }
+
+    let time_line = AnimationTimeLine {
+        key_frames: vec![AnimationKeyFrame {
+            begin: 0f32,
+            end: 1f32,
+            from: param.get::<_, AnimationValue>("from")?,
+            to: param.get::<_, AnimationValue>("to")?,
+            easing: param
+                .get::<_, Option<AnimationEasing>>("easing")?
+                .unwrap_or_default(),
+            color_space: param
+                .get::<_, Option<AnimationColorSpace>>("color_space")?
+                .unwrap_or_default(),
+        }],
+        transform: param.get("transform")?,
+        component: param.get("component")?,
+        field: param.get("field")?,
+    };
+    let mut tween = Tween::new(entity.entity(), time_line, duration);
+
+    tween.delay = delay;
+    // Negative counts repeat forever.
+    tween.repeat = match param.get::<_, Option<i64>>("repeat")? {
+        Some(repeat) if repeat < 0 => None,
+        Some(repeat) => Some(repeat as u32),
+        None => Some(0),
+    };
+    tween.pingpong = param.get::<_, Option<bool>>("pingpong")?.unwrap_or(false);
+    tween.on_complete = param
+        .get::<_, Option<LuaFunction>>("on_complete")?
+        .map(|function| lua.create_registry_value(function))
+        .transpose()?;
+
+    Ok(tween)
+}
+
+fn release_callbacks(lua: &Lua, tweens: Vec<Tween>) {
+    for tween in tweens {
+        if let Some(on_complete) = tween.on_complete {
+            lua.remove_registry_value(on_complete).ok();
+        }
+    }
+}
@@ -7,7 +7,7 @@ use std::io::Error as IOError;
 pub enum AssetLoadError {
     UnsupportedAssetType(&'static str),
     IOError(IOError),
-    Other(Box<dyn Error>),
+    Other(Box<dyn Error + Send + Sync>),
 }
 
 impl AssetLoadError {
@@ -15,7 +15,7 @@ impl AssetLoadError {
         Self::UnsupportedAssetType(type_name::<T>())
     }
 
-    pub fn other<T: 'static + Into<Box<dyn Error>>>(err: T) -> Self {
+    pub fn other<T: 'static + Into<Box<dyn Error + Send + Sync>>>(err: T) -> Self {
         Self::Other(err.into())
     }
 }
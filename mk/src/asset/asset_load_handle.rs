@@ -0,0 +1,153 @@
+use crate::animation::{Animation, LuaRcAnimation};
+use crate::render::{
+    LuaRcFont, LuaRcShader, LuaRcSprite, LuaRcSpriteAtlas, LuaRcSpriteAtlasGrid,
+    LuaRcSpriteNinePatch, LuaRcTilemap, Shader, Sprite, SpriteAtlas, SpriteAtlasGrid,
+    SpriteNinePatch, Tilemap,
+};
+use fontdue::Font;
+use mlua::prelude::*;
+use std::any::{type_name, Any, TypeId};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub enum AssetLoadState {
+    Loading,
+    Loaded(Arc<dyn Any + Send + Sync>),
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct AssetLoadHandle {
+    id: u64,
+    type_id: TypeId,
+    path: PathBuf,
+    state: Rc<RefCell<AssetLoadState>>,
+}
+
+impl AssetLoadHandle {
+    pub fn new(id: u64, type_id: TypeId, path: PathBuf) -> Self {
+        Self {
+            id,
+            type_id,
+            path,
+            state: Rc::new(RefCell::new(AssetLoadState::Loading)),
+        }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn state(&self) -> AssetLoadState {
+        self.state.borrow().clone()
+    }
+
+    pub fn set_state(&self, state: AssetLoadState) {
+        *self.state.borrow_mut() = state;
+    }
+
+    pub fn is_loading(&self) -> bool {
+        matches!(*self.state.borrow(), AssetLoadState::Loading)
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        matches!(*self.state.borrow(), AssetLoadState::Loaded(..))
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(*self.state.borrow(), AssetLoadState::Failed(..))
+    }
+
+    pub fn error(&self) -> Option<String> {
+        match &*self.state.borrow() {
+            AssetLoadState::Failed(err) => Some(err.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn asset<T>(&self) -> Option<Arc<T>>
+    where
+        T: 'static + Any + Send + Sync,
+    {
+        match &*self.state.borrow() {
+            AssetLoadState::Loaded(asset) => asset.clone().downcast::<T>().ok(),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for AssetLoadHandle {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for AssetLoadHandle {}
+
+impl LuaUserData for AssetLoadHandle {
+    fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_meta_method(LuaMetaMethod::Eq, |_lua, this, other: LuaAnyUserData| {
+            Ok(match other.borrow::<Self>() {
+                Ok(other) => *this == *other,
+                Err(..) => false,
+            })
+        });
+        methods.add_meta_method(LuaMetaMethod::Index, |lua, this, value: String| {
+            Ok(match value.as_str() {
+                "id" => this.id.to_lua(lua)?,
+                "path" => this.path.to_string_lossy().to_lua(lua)?,
+                "is_loading" => this.is_loading().to_lua(lua)?,
+                "is_loaded" => this.is_loaded().to_lua(lua)?,
+                "is_failed" => this.is_failed().to_lua(lua)?,
+                "error" => this.error().to_lua(lua)?,
+                "asset" => match this.state() {
+                    AssetLoadState::Loaded(asset) => asset_to_lua(lua, &asset)?,
+                    _ => LuaValue::Nil,
+                },
+                _ => {
+                    return Err(format!(
+                        "property '{}' is not exists on the '{}'",
+                        value,
+                        type_name::<Self>()
+                    )
+                    .to_lua_err())
+                }
+            })
+        });
+    }
+}
+
+pub fn asset_to_lua<'lua>(
+    lua: &'lua Lua,
+    asset: &Arc<dyn Any + Send + Sync>,
+) -> LuaResult<LuaValue<'lua>> {
+    macro_rules! to_lua_as {
+        ($ty:ty, $lua_rc:ident) => {
+            if let Ok(asset) = asset.clone().downcast::<$ty>() {
+                return $lua_rc::from(asset).to_lua(lua);
+            }
+        };
+    }
+
+    to_lua_as!(Animation, LuaRcAnimation);
+    to_lua_as!(Font, LuaRcFont);
+    to_lua_as!(Shader, LuaRcShader);
+    to_lua_as!(Sprite, LuaRcSprite);
+    to_lua_as!(SpriteAtlas, LuaRcSpriteAtlas);
+    to_lua_as!(SpriteAtlasGrid, LuaRcSpriteAtlasGrid);
+    to_lua_as!(SpriteNinePatch, LuaRcSpriteNinePatch);
+    to_lua_as!(Tilemap, LuaRcTilemap);
+
+    Ok(LuaValue::Nil)
+}
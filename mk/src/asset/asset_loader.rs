@@ -1,13 +1,26 @@
-use crate::asset::{AssetLoadError, AssetManager, BaseAssetLoader};
+use crate::asset::{AssetLoadError, AssetManager, AssetSource, BaseAssetLoader};
 use std::any::{Any, TypeId};
 use std::path::Path;
 use std::sync::Arc;
 
+pub type AssetDecoder =
+    dyn Fn(&dyn AssetSource, &Path) -> Result<Box<dyn Any + Send>, AssetLoadError> + Send + Sync;
+pub type AssetFinisher<T> = dyn Fn(
+        &AssetManager,
+        &dyn AssetSource,
+        &Path,
+        Box<dyn Any + Send>,
+    ) -> Result<Arc<T>, AssetLoadError>
+    + Sync;
+
 pub struct AssetLoader<T>
 where
     T: 'static + Any + Send + Sync,
 {
-    loader: Box<dyn Fn(&AssetManager, &Path, &Path) -> Result<Arc<T>, AssetLoadError> + Sync>,
+    // Runs on a worker thread when loaded asynchronously; absent if the loader is main-thread only.
+    decoder: Option<Arc<AssetDecoder>>,
+    // Always runs on the main thread, e.g. to upload decoded data to the GPU.
+    finisher: Box<AssetFinisher<T>>,
 }
 
 impl<T> AssetLoader<T>
@@ -16,20 +29,58 @@ where
 {
     pub fn new<F>(loader: F) -> Self
     where
-        F: 'static + Fn(&AssetManager, &Path, &Path) -> Result<Arc<T>, AssetLoadError> + Sync,
+        F: 'static
+            + Fn(&AssetManager, &dyn AssetSource, &Path) -> Result<Arc<T>, AssetLoadError>
+            + Sync,
+    {
+        Self {
+            decoder: None,
+            finisher: Box::new(move |asset_mgr, source, path, _| loader(asset_mgr, source, path)),
+        }
+    }
+
+    pub fn new_async<D, FD, FF>(decoder: FD, finisher: FF) -> Self
+    where
+        D: 'static + Send,
+        FD: 'static + Fn(&dyn AssetSource, &Path) -> Result<D, AssetLoadError> + Send + Sync,
+        FF: 'static + Fn(&AssetManager, &Path, D) -> Result<Arc<T>, AssetLoadError> + Sync,
     {
         Self {
-            loader: Box::new(loader),
+            decoder: Some(Arc::new(move |source, path| {
+                Ok(Box::new(decoder(source, path)?) as Box<dyn Any + Send>)
+            })),
+            finisher: Box::new(move |asset_mgr, _source, path, decoded| {
+                finisher(asset_mgr, path, *decoded.downcast::<D>().unwrap())
+            }),
         }
     }
 
+    pub fn decoder(&self) -> Option<&Arc<AssetDecoder>> {
+        self.decoder.as_ref()
+    }
+
     pub fn load<P: AsRef<Path>>(
         &self,
         asset_mgr: &AssetManager,
-        base: P,
+        source: &dyn AssetSource,
+        path: P,
+    ) -> Result<Arc<T>, AssetLoadError> {
+        let decoded = match &self.decoder {
+            Some(decoder) => decoder.as_ref()(source, path.as_ref())?,
+            None => Box::new(()),
+        };
+
+        self.finish(asset_mgr, source, path, decoded)
+    }
+
+    pub fn finish<P: AsRef<Path>>(
+        &self,
+        asset_mgr: &AssetManager,
+        source: &dyn AssetSource,
         path: P,
+        decoded: Box<dyn Any + Send>,
     ) -> Result<Arc<T>, AssetLoadError> {
-        self.loader.as_ref()(asset_mgr, base.as_ref(), path.as_ref())
+        self.finisher.as_ref()(asset_mgr, source, path.as_ref(), decoded)
     }
 }
 
@@ -1,22 +1,94 @@
 use crate::asset::{
-    AssetCacheManager, AssetLoadError, AssetLoader, BaseAssetCacheManager, BaseAssetLoader,
+    AssetCacheManager, AssetLoadError, AssetLoadHandle, AssetLoadState, AssetLoader, AssetReload,
+    AssetSource, AssetWorkerPool, BaseAssetCacheManager, BaseAssetLoader, RecordingAssetSource,
 };
-use std::any::{Any, TypeId};
+use crate::{emit_diagnostic_error, emit_diagnostic_info};
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use std::any::{type_name, Any, TypeId};
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+type AsyncDecoded = (
+    u64,
+    Result<Box<dyn Any + Send>, AssetLoadError>,
+    Vec<PathBuf>,
+);
+type AsyncFinisher = fn(
+    &AssetManager,
+    &Path,
+    Box<dyn Any + Send>,
+    Vec<PathBuf>,
+) -> Result<Arc<dyn Any + Send + Sync>, AssetLoadError>;
+type Reloader = fn(&AssetManager, &Path) -> Option<AssetReload>;
+
+const HOT_RELOAD_INTERVAL: Duration = Duration::from_millis(500);
+
+struct PendingAssetLoad {
+    handle: AssetLoadHandle,
+    finisher: AsyncFinisher,
+}
+
+struct WatchedAsset {
+    dependencies: Vec<(PathBuf, Option<SystemTime>)>,
+    reloader: Reloader,
+}
 
 pub struct AssetManager {
-    base: PathBuf,
+    source: Arc<dyn AssetSource>,
     types: HashMap<TypeId, (Box<dyn BaseAssetCacheManager>, Box<dyn BaseAssetLoader>)>,
+    workers: RefCell<Option<AssetWorkerPool>>,
+    next_load_id: Cell<u64>,
+    pending_loads: RefCell<HashMap<u64, PendingAssetLoad>>,
+    completed_loads: RefCell<Vec<AssetLoadHandle>>,
+    decoded_sender: Sender<AsyncDecoded>,
+    decoded_receiver: Receiver<AsyncDecoded>,
+    hot_reload: bool,
+    last_hot_reload: Cell<Instant>,
+    watched: RefCell<HashMap<(TypeId, PathBuf), WatchedAsset>>,
 }
 
 impl AssetManager {
-    pub fn new(base: PathBuf) -> AssetManager {
+    pub fn new(source: Box<dyn AssetSource>) -> AssetManager {
+        let (decoded_sender, decoded_receiver) = unbounded();
+
         AssetManager {
-            base,
+            source: source.into(),
             types: HashMap::new(),
+            workers: RefCell::new(None),
+            next_load_id: Cell::new(0),
+            pending_loads: RefCell::new(HashMap::new()),
+            completed_loads: RefCell::new(Vec::new()),
+            decoded_sender,
+            decoded_receiver,
+            hot_reload: false,
+            last_hot_reload: Cell::new(Instant::now()),
+            watched: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn source(&self) -> &dyn AssetSource {
+        self.source.as_ref()
+    }
+
+    pub fn set_source(&mut self, source: Box<dyn AssetSource>) {
+        self.source = source.into();
+    }
+
+    pub fn hot_reload(&self) -> bool {
+        self.hot_reload
+    }
+
+    // Reloads assets whose source files have been modified; only sources reporting modification times are watched.
+    pub fn set_hot_reload(&mut self, hot_reload: bool) {
+        self.hot_reload = hot_reload;
+
+        if !hot_reload {
+            self.watched.borrow_mut().clear();
         }
     }
 
@@ -30,30 +102,262 @@ impl AssetManager {
         );
     }
 
-    // TODO: Provide async-way to load assets.
     pub fn load<T>(&self, path: impl AsRef<Path>) -> Result<Arc<T>, AssetLoadError>
     where
         T: 'static + Debug + Any + Send + Sync,
     {
-        match self.types.get(&TypeId::of::<T>()) {
-            Some((cache, loader)) => {
-                let cache = cache.downcast_ref::<AssetCacheManager<T>>().unwrap();
-
-                match cache.load(&path) {
-                    Some(asset) => Ok(asset),
-                    None => {
-                        let asset = loader.downcast_ref::<AssetLoader<T>>().unwrap().load(
-                            self,
-                            self.base.as_path(),
-                            path.as_ref(),
-                        )?;
-
-                        cache.cache(path.as_ref().to_path_buf(), Arc::downgrade(&asset));
-                        Ok(asset)
-                    }
+        let (cache, loader) = self.get::<T>()?;
+
+        match cache.load(&path) {
+            Some(asset) => Ok(asset),
+            None => {
+                let source = RecordingAssetSource::new(self.source());
+                let asset = loader.load(self, &source, path.as_ref())?;
+                self.watch::<T>(path.as_ref(), source.into_paths());
+                cache.cache(path.as_ref().to_path_buf(), Arc::downgrade(&asset));
+                Ok(asset)
+            }
+        }
+    }
+
+    // Decodes the asset on a worker thread; the load is finished on the main thread by `poll_async`.
+    pub fn load_async<T>(&self, path: impl AsRef<Path>) -> Result<AssetLoadHandle, AssetLoadError>
+    where
+        T: 'static + Debug + Any + Send + Sync,
+    {
+        let (cache, loader) = self.get::<T>()?;
+        let path = path.as_ref().to_path_buf();
+        let id = self.next_load_id.get();
+        self.next_load_id.set(id + 1);
+
+        let handle = AssetLoadHandle::new(id, TypeId::of::<T>(), path.clone());
+
+        if let Some(asset) = cache.load(&path) {
+            handle.set_state(AssetLoadState::Loaded(asset));
+            self.completed_loads.borrow_mut().push(handle.clone());
+            return Ok(handle);
+        }
+
+        self.pending_loads.borrow_mut().insert(
+            id,
+            PendingAssetLoad {
+                handle: handle.clone(),
+                finisher: Self::finish_async::<T>,
+            },
+        );
+
+        match loader.decoder() {
+            Some(decoder) => {
+                let decoder = decoder.clone();
+                let source = self.source.clone();
+                let sender = self.decoded_sender.clone();
+
+                self.workers
+                    .borrow_mut()
+                    .get_or_insert_with(AssetWorkerPool::with_default_worker_count)
+                    .execute(move || {
+                        let source = RecordingAssetSource::new(source.as_ref());
+                        // The load fails instead of staying pending forever if the decoder panics.
+                        let decoded =
+                            catch_unwind(AssertUnwindSafe(|| decoder.as_ref()(&source, &path)))
+                                .unwrap_or_else(|_| {
+                                    Err(AssetLoadError::other("the decoder has panicked"))
+                                });
+                        sender.send((id, decoded, source.into_paths())).ok();
+                    });
+            }
+            None => {
+                self.decoded_sender
+                    .send((id, Ok(Box::new(())), Vec::new()))
+                    .ok();
+            }
+        }
+
+        Ok(handle)
+    }
+
+    // Finishes the decoded asynchronous loads and returns every handle completed since the last call.
+    pub fn poll_async(&self) -> Vec<AssetLoadHandle> {
+        let mut completed = self.completed_loads.take();
+
+        for (id, decoded, dependencies) in self.decoded_receiver.try_iter() {
+            let pending = match self.pending_loads.borrow_mut().remove(&id) {
+                Some(pending) => pending,
+                None => continue,
+            };
+            let path = pending.handle.path().to_path_buf();
+
+            match decoded.and_then(|decoded| (pending.finisher)(self, &path, decoded, dependencies))
+            {
+                Ok(asset) => pending.handle.set_state(AssetLoadState::Loaded(asset)),
+                Err(err) => {
+                    emit_diagnostic_error!(format!(
+                        "unable to load asset '{}' asynchronously due to: {}",
+                        path.display(),
+                        err
+                    ));
+                    pending
+                        .handle
+                        .set_state(AssetLoadState::Failed(err.to_string()));
+                }
+            }
+
+            completed.push(pending.handle);
+        }
+
+        completed
+    }
+
+    // Reloads every watched asset that is still alive and has modified dependencies.
+    pub fn poll_hot_reload(&self) -> Vec<AssetReload> {
+        if !self.hot_reload || self.last_hot_reload.get().elapsed() < HOT_RELOAD_INTERVAL {
+            return Vec::new();
+        }
+
+        self.last_hot_reload.set(Instant::now());
+
+        let modified = self
+            .watched
+            .borrow()
+            .iter()
+            .filter(|(_, watched)| {
+                watched
+                    .dependencies
+                    .iter()
+                    .any(|(path, modified)| self.source.modified(path) != *modified)
+            })
+            .map(|((type_id, path), watched)| (*type_id, path.clone(), watched.reloader))
+            .collect::<Vec<_>>();
+        let mut reloads = Vec::with_capacity(modified.len());
+
+        for (type_id, path, reloader) in modified {
+            match reloader(self, &path) {
+                Some(reload) => reloads.push(reload),
+                None => {
+                    // No one is using the asset anymore.
+                    self.watched.borrow_mut().remove(&(type_id, path));
                 }
             }
+        }
+
+        reloads
+    }
+
+    fn get<T>(&self) -> Result<(&AssetCacheManager<T>, &AssetLoader<T>), AssetLoadError>
+    where
+        T: 'static + Debug + Any + Send + Sync,
+    {
+        match self.types.get(&TypeId::of::<T>()) {
+            Some((cache, loader)) => Ok((
+                cache.downcast_ref::<AssetCacheManager<T>>().unwrap(),
+                loader.downcast_ref::<AssetLoader<T>>().unwrap(),
+            )),
             None => Err(AssetLoadError::unsupported::<T>()),
         }
     }
+
+    fn finish_async<T>(
+        &self,
+        path: &Path,
+        decoded: Box<dyn Any + Send>,
+        mut dependencies: Vec<PathBuf>,
+    ) -> Result<Arc<dyn Any + Send + Sync>, AssetLoadError>
+    where
+        T: 'static + Debug + Any + Send + Sync,
+    {
+        let (cache, loader) = self.get::<T>()?;
+
+        // The same asset may have been loaded while this one was being decoded.
+        if let Some(asset) = cache.load(path) {
+            return Ok(asset);
+        }
+
+        let source = RecordingAssetSource::new(self.source());
+        let asset = loader.finish(self, &source, path, decoded)?;
+        dependencies.extend(source.into_paths());
+        self.watch::<T>(path, dependencies);
+        cache.cache(PathBuf::from(path), Arc::downgrade(&asset));
+        Ok(asset)
+    }
+
+    fn watch<T>(&self, path: &Path, dependencies: Vec<PathBuf>)
+    where
+        T: 'static + Debug + Any + Send + Sync,
+    {
+        if !self.hot_reload {
+            return;
+        }
+
+        let dependencies = dependencies
+            .into_iter()
+            .map(|dependency| {
+                let modified = self.source.modified(&dependency);
+                (dependency, modified)
+            })
+            .collect();
+
+        self.watched.borrow_mut().insert(
+            (TypeId::of::<T>(), path.to_path_buf()),
+            WatchedAsset {
+                dependencies,
+                reloader: Self::reload::<T>,
+            },
+        );
+    }
+
+    fn reload<T>(&self, path: &Path) -> Option<AssetReload>
+    where
+        T: 'static + Debug + Any + Send + Sync,
+    {
+        let (cache, loader) = self.get::<T>().ok()?;
+        let old = cache.load(path)?;
+        let source = RecordingAssetSource::new(self.source());
+        let result = loader.load(self, &source, path);
+        let mut dependencies = source.into_paths();
+
+        let result = match result {
+            Ok(asset) => {
+                emit_diagnostic_info!(format!(
+                    "asset '{}' of type {} has been reloaded.",
+                    path.display(),
+                    type_name::<T>()
+                ));
+                cache.cache(path.to_path_buf(), Arc::downgrade(&asset));
+                Ok(asset as Arc<dyn Any + Send + Sync>)
+            }
+            Err(err) => {
+                emit_diagnostic_error!(format!(
+                    "unable to reload asset '{}' of type {} due to: {}",
+                    path.display(),
+                    type_name::<T>(),
+                    err
+                ));
+
+                // Keep watching the previous dependencies too, as the failed load may not have read them all.
+                if let Some(watched) = self
+                    .watched
+                    .borrow()
+                    .get(&(TypeId::of::<T>(), path.to_path_buf()))
+                {
+                    for (dependency, _) in &watched.dependencies {
+                        if !dependencies.contains(dependency) {
+                            dependencies.push(dependency.clone());
+                        }
+                    }
+                }
+
+                Err(err.to_string())
+            }
+        };
+
+        self.watch::<T>(path, dependencies);
+
+        Some(AssetReload {
+            type_id: TypeId::of::<T>(),
+            type_name: type_name::<T>(),
+            path: path.to_path_buf(),
+            old,
+            result,
+        })
+    }
 }
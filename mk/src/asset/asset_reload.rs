@@ -0,0 +1,33 @@
+use std::any::{Any, TypeId};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct AssetReload {
+    pub type_id: TypeId,
+    pub type_name: &'static str,
+    pub path: PathBuf,
+    pub old: Arc<dyn Any + Send + Sync>,
+    pub result: Result<Arc<dyn Any + Send + Sync>, String>,
+}
+
+impl AssetReload {
+    pub fn is_failed(&self) -> bool {
+        self.result.is_err()
+    }
+
+    // Returns the reloaded asset if the given one is the version that has been replaced.
+    pub fn replacement<T>(&self, asset: &Arc<T>) -> Option<Arc<T>>
+    where
+        T: 'static + Any + Send + Sync,
+    {
+        if Arc::as_ptr(asset) as *const () != Arc::as_ptr(&self.old) as *const () {
+            return None;
+        }
+
+        match &self.result {
+            Ok(new) => new.clone().downcast::<T>().ok(),
+            Err(..) => None,
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use crate::asset::AssetLoadError;
+use res::asset_loader::{BaseResource, ResourceDecoder, ResourceLoadError, ResourceLoader};
+use res::decoder::{SpriteDecoder, TextDecoder, TextDecoderOutput};
+use res::meta_loader::{load_resource_meta, MetaLoadError};
+use res::{ResourcesMeta, META_FILENAME};
+use std::fmt::{Display, Result as FmtResult};
+use std::fs::{metadata as fs_metadata, read as fs_read};
+use std::io::{Error as IOError, ErrorKind as IOErrorKind};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+pub trait AssetSource: Send + Sync {
+    fn exists(&self, path: &Path) -> bool;
+    fn read(&self, path: &Path) -> Result<Vec<u8>, AssetLoadError>;
+
+    fn load_resource(&self, _path: &Path) -> Result<Option<Arc<dyn BaseResource>>, AssetLoadError> {
+        Ok(None)
+    }
+
+    fn modified(&self, _path: &Path) -> Option<SystemTime> {
+        None
+    }
+
+    fn read_to_string(&self, path: &Path) -> Result<String, AssetLoadError> {
+        String::from_utf8(self.read(path)?).map_err(AssetLoadError::other)
+    }
+
+    fn find_with_extensions(&self, path: &Path, extensions: &[&str]) -> Option<PathBuf> {
+        extensions
+            .iter()
+            .map(|ext| path.with_extension(ext))
+            .find(|path| self.exists(path))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileSystemAssetSource {
+    base: PathBuf,
+}
+
+impl FileSystemAssetSource {
+    pub fn new(base: impl Into<PathBuf>) -> Self {
+        Self { base: base.into() }
+    }
+
+    pub fn base(&self) -> &Path {
+        &self.base
+    }
+}
+
+impl AssetSource for FileSystemAssetSource {
+    fn exists(&self, path: &Path) -> bool {
+        match fs_metadata(self.base.join(path)) {
+            Ok(metadata) => metadata.is_file(),
+            Err(..) => false,
+        }
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<u8>, AssetLoadError> {
+        Ok(fs_read(self.base.join(path))?)
+    }
+
+    fn modified(&self, path: &Path) -> Option<SystemTime> {
+        fs_metadata(self.base.join(path))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+}
+
+#[derive(Debug)]
+pub enum ArchiveAssetSourceError {
+    IOError(IOError),
+    MetaLoadError(MetaLoadError),
+    ResourceLoadError(ResourceLoadError),
+    UnexpectedResourceType(String, String),
+}
+
+impl Display for ArchiveAssetSourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> FmtResult {
+        match self {
+            Self::IOError(err) => err.fmt(f),
+            Self::MetaLoadError(err) => write!(f, "unable to load resource meta: {:?}", err),
+            Self::ResourceLoadError(err) => write!(f, "unable to load resource: {:?}", err),
+            Self::UnexpectedResourceType(name, ty) => write!(
+                f,
+                "resource '{}' has unexpected type '{}' to be read as raw bytes",
+                name, ty
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ArchiveAssetSourceError {}
+
+impl From<IOError> for ArchiveAssetSourceError {
+    fn from(err: IOError) -> Self {
+        Self::IOError(err)
+    }
+}
+
+impl From<MetaLoadError> for ArchiveAssetSourceError {
+    fn from(err: MetaLoadError) -> Self {
+        Self::MetaLoadError(err)
+    }
+}
+
+impl From<ResourceLoadError> for ArchiveAssetSourceError {
+    fn from(err: ResourceLoadError) -> Self {
+        Self::ResourceLoadError(err)
+    }
+}
+
+impl From<ArchiveAssetSourceError> for AssetLoadError {
+    fn from(err: ArchiveAssetSourceError) -> Self {
+        Self::other(err)
+    }
+}
+
+pub struct ArchiveAssetSource {
+    loader: ResourceLoader,
+    meta: ResourcesMeta,
+}
+
+impl ArchiveAssetSource {
+    pub fn new(
+        key: impl AsRef<[u8]>,
+        salt: impl AsRef<[u8]>,
+        base: impl AsRef<Path>,
+        meta: ResourcesMeta,
+    ) -> Result<Self, ArchiveAssetSourceError> {
+        let mut loader = ResourceLoader::new(key, salt, base)?;
+        loader.add_decoder(Box::new(SpriteDecoder));
+        loader.add_decoder(Box::new(TextDecoder));
+
+        Ok(Self { loader, meta })
+    }
+
+    pub fn from_dir(
+        key: impl AsRef<[u8]>,
+        salt: impl AsRef<[u8]>,
+        base: impl AsRef<Path>,
+    ) -> Result<Self, ArchiveAssetSourceError> {
+        let meta = load_resource_meta(&fs_read(base.as_ref().join(META_FILENAME))?)?;
+        Self::new(key, salt, base, meta)
+    }
+
+    pub fn add_decoder(&mut self, decoder: Box<dyn ResourceDecoder>) {
+        self.loader.add_decoder(decoder);
+    }
+
+    pub fn meta(&self) -> &ResourcesMeta {
+        &self.meta
+    }
+
+    pub fn resource_name(path: &Path) -> String {
+        path.components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    fn load(&self, path: &Path) -> Result<Arc<dyn BaseResource>, AssetLoadError> {
+        let name = Self::resource_name(path);
+        let index = match self.meta.resource_names.get(&name) {
+            Some(&index) => index,
+            None => {
+                return Err(IOError::new(
+                    IOErrorKind::NotFound,
+                    format!("cannot find a resource named '{}'", name),
+                )
+                .into())
+            }
+        };
+
+        Ok(self
+            .loader
+            .load(&self.meta.resources[index])
+            .map_err(ArchiveAssetSourceError::from)?)
+    }
+}
+
+impl AssetSource for ArchiveAssetSource {
+    fn exists(&self, path: &Path) -> bool {
+        self.meta
+            .resource_names
+            .contains_key(&Self::resource_name(path))
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<u8>, AssetLoadError> {
+        let resource = self.load(path)?;
+
+        match resource.downcast_ref::<TextDecoderOutput>() {
+            Some(output) => Ok(output.content.clone()),
+            None => Err(ArchiveAssetSourceError::UnexpectedResourceType(
+                Self::resource_name(path),
+                resource.ty().to_owned(),
+            )
+            .into()),
+        }
+    }
+
+    fn load_resource(&self, path: &Path) -> Result<Option<Arc<dyn BaseResource>>, AssetLoadError> {
+        self.load(path).map(Some)
+    }
+}
+
+// Records every path read through it, so that the asset can be reloaded once any of them changes.
+pub struct RecordingAssetSource<'a> {
+    source: &'a dyn AssetSource,
+    paths: Mutex<Vec<PathBuf>>,
+}
+
+impl<'a> RecordingAssetSource<'a> {
+    pub fn new(source: &'a dyn AssetSource) -> Self {
+        Self {
+            source,
+            paths: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn into_paths(self) -> Vec<PathBuf> {
+        self.paths.into_inner().unwrap()
+    }
+
+    fn record(&self, path: &Path) {
+        let mut paths = self.paths.lock().unwrap();
+
+        if !paths.iter().any(|recorded| recorded == path) {
+            paths.push(path.to_path_buf());
+        }
+    }
+}
+
+impl<'a> AssetSource for RecordingAssetSource<'a> {
+    // Probed paths are recorded too, so that a file appearing later at one of them is noticed.
+    fn exists(&self, path: &Path) -> bool {
+        self.record(path);
+        self.source.exists(path)
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<u8>, AssetLoadError> {
+        self.record(path);
+        self.source.read(path)
+    }
+
+    fn load_resource(&self, path: &Path) -> Result<Option<Arc<dyn BaseResource>>, AssetLoadError> {
+        self.record(path);
+        self.source.load_resource(path)
+    }
+
+    fn modified(&self, path: &Path) -> Option<SystemTime> {
+        self.source.modified(path)
+    }
+}
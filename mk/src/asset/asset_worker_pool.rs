@@ -0,0 +1,86 @@
+use crossbeam_channel::{unbounded, Sender};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::thread::{available_parallelism, Builder, JoinHandle};
+
+pub type AssetJob = Box<dyn FnOnce() + Send>;
+
+pub struct AssetWorkerPool {
+    sender: Option<Sender<AssetJob>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl AssetWorkerPool {
+    pub fn new(worker_count: usize) -> Self {
+        let (sender, receiver) = unbounded::<AssetJob>();
+        let workers = (0..worker_count)
+            .filter_map(|index| {
+                let receiver = receiver.clone();
+                Builder::new()
+                    .name(format!("asset-worker-{}", index))
+                    .spawn(move || {
+                        // A panicking job must not take the worker down with it.
+                        for job in receiver {
+                            catch_unwind(AssertUnwindSafe(job)).ok();
+                        }
+                    })
+                    .ok()
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            // Jobs are executed in place on platforms that cannot spawn threads.
+            sender: if workers.is_empty() {
+                None
+            } else {
+                Some(sender)
+            },
+            workers,
+        }
+    }
+
+    pub fn with_default_worker_count() -> Self {
+        Self::new(
+            available_parallelism()
+                .map_or(1, |count| count.get())
+                .clamp(1, 4),
+        )
+    }
+
+    pub fn worker_count(&self) -> usize {
+        self.workers.len()
+    }
+
+    pub fn execute(&self, job: impl 'static + FnOnce() + Send) {
+        match &self.sender {
+            Some(sender) => sender.send(Box::new(job)).unwrap(),
+            None => job(),
+        }
+    }
+}
+
+impl Drop for AssetWorkerPool {
+    fn drop(&mut self) {
+        // Dropping the sender lets every worker leave its loop once the queued jobs are done.
+        self.sender.take();
+
+        for worker in self.workers.drain(..) {
+            worker.join().ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workers_survive_panicking_jobs() {
+        let pool = AssetWorkerPool::new(1);
+        let (sender, receiver) = unbounded();
+
+        pool.execute(|| panic!("a decoder has panicked"));
+        pool.execute(move || sender.send(()).unwrap());
+
+        assert!(receiver.recv().is_ok());
+    }
+}
@@ -0,0 +1,43 @@
+use crate::animation::Animation;
+use crate::asset::{AssetLoadError, AssetLoader};
+use std::path::Path;
+use std::sync::Arc;
+
+pub fn animation_loader() -> AssetLoader<Animation> {
+    AssetLoader::new_async(
+        |source, path| {
+            let animation: Animation = serde_json::from_str(
+                &source
+                    .read_to_string(&Path::new("animations").join(path).with_extension("json"))?,
+            )?;
+
+            if animation.duration <= 0f32 {
+                return Err(AssetLoadError::from(
+                    "animation's duration must be greater than zero",
+                ));
+            }
+
+            for time_line in &animation.time_lines {
+                for key_frame in &time_line.key_frames {
+                    if !(0f32 <= key_frame.begin
+                        && key_frame.begin < key_frame.end
+                        && key_frame.end <= 1f32)
+                    {
+                        return Err(AssetLoadError::from(
+                            "key frame's begin and end must be normalized and in order",
+                        ));
+                    }
+                }
+            }
+
+            for event in &animation.events {
+                if !(0f32..=1f32).contains(&event.time) {
+                    return Err(AssetLoadError::from("event's time must be normalized"));
+                }
+            }
+
+            Ok(animation)
+        },
+        |_asset_mgr, _path, animation| Ok(Arc::new(animation)),
+    )
+}
@@ -1,3 +1,4 @@
+mod animation_loader;
 mod font_loader;
 mod shader_loader;
 mod sprite_atlas_grid_loader;
@@ -6,6 +7,7 @@ mod sprite_loader;
 mod sprite_nine_patch_loader;
 mod tilemap_loader;
 
+pub use animation_loader::*;
 pub use font_loader::*;
 pub use shader_loader::*;
 pub use sprite_atlas_grid_loader::*;
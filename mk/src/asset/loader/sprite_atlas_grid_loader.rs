@@ -1,5 +1,8 @@
+use crate::asset::loader::load_image;
 use crate::asset::{AssetLoadError, AssetLoader};
 use crate::render::{SpriteAtlasGrid, SpriteAtlasGridError};
+use std::path::Path;
+use std::sync::Arc;
 
 impl From<SpriteAtlasGridError> for AssetLoadError {
     fn from(err: SpriteAtlasGridError) -> Self {
@@ -8,7 +11,14 @@ impl From<SpriteAtlasGridError> for AssetLoadError {
 }
 
 pub fn sprite_atlas_grid_loader() -> AssetLoader<SpriteAtlasGrid> {
-    AssetLoader::new(|_asset_mgr, base, path| {
-        Ok(SpriteAtlasGrid::from_file(&base.join("sprites").join(path), None)?.into())
-    })
+    AssetLoader::new_async(
+        |source, path| {
+            let path = Path::new("sprites").join(path);
+            let metadata = source.read_to_string(&path.with_extension("meta.json"))?;
+            Ok((load_image(source, &path)?, metadata))
+        },
+        |_asset_mgr, _path, (image, metadata)| {
+            Ok(Arc::new(SpriteAtlasGrid::from_resource(&image, &metadata)?))
+        },
+    )
 }
@@ -1,5 +1,9 @@
-use crate::asset::{AssetLoadError, AssetLoader};
+use crate::asset::{AssetLoadError, AssetLoader, AssetSource};
 use crate::render::{Sprite, SpriteError};
+use res::decoder::{decode_image, SpriteDecoderOutput, TextDecoderOutput};
+use std::io::{Error as IOError, ErrorKind as IOErrorKind};
+use std::path::Path;
+use std::sync::Arc;
 
 impl From<SpriteError> for AssetLoadError {
     fn from(err: SpriteError) -> Self {
@@ -8,7 +12,30 @@ impl From<SpriteError> for AssetLoadError {
 }
 
 pub fn sprite_loader() -> AssetLoader<Sprite> {
-    AssetLoader::new(|_asset_mgr, base, path| {
-        Ok(Sprite::from_file(&base.join("sprites").join(path), None)?.into())
-    })
+    AssetLoader::new_async(
+        |source, path| load_image(source, &Path::new("sprites").join(path)),
+        |_asset_mgr, _path, image| Ok(Arc::new(Sprite::from_resource(&image))),
+    )
+}
+
+// Images packed as sprite resources are already decoded; anything else is decoded from its bytes.
+pub(crate) fn load_image(
+    source: &dyn AssetSource,
+    path: &Path,
+) -> Result<SpriteDecoderOutput, AssetLoadError> {
+    let image_path = source
+        .find_with_extensions(path, &["png", "jpg", "jpeg", "gif"])
+        .ok_or_else(|| IOError::new(IOErrorKind::NotFound, "cannot find a image"))?;
+
+    if let Some(resource) = source.load_resource(&image_path)? {
+        if let Some(resource) = resource.downcast_ref::<SpriteDecoderOutput>() {
+            return Ok(resource.clone());
+        }
+
+        if let Some(resource) = resource.downcast_ref::<TextDecoderOutput>() {
+            return Ok(decode_image(&resource.content, None).map_err(SpriteError::from)?);
+        }
+    }
+
+    Ok(decode_image(&source.read(&image_path)?, None).map_err(SpriteError::from)?)
 }
@@ -1,5 +1,8 @@
+use crate::asset::loader::load_image;
 use crate::asset::{AssetLoadError, AssetLoader};
 use crate::render::{SpriteNinePatch, SpriteNinePatchError};
+use std::path::Path;
+use std::sync::Arc;
 
 impl From<SpriteNinePatchError> for AssetLoadError {
     fn from(err: SpriteNinePatchError) -> Self {
@@ -8,7 +11,14 @@ impl From<SpriteNinePatchError> for AssetLoadError {
 }
 
 pub fn sprite_nine_patch_loader() -> AssetLoader<SpriteNinePatch> {
-    AssetLoader::new(|_asset_mgr, base, path| {
-        Ok(SpriteNinePatch::from_file(&base.join("nine-patches").join(path), None)?.into())
-    })
+    AssetLoader::new_async(
+        |source, path| {
+            let path = Path::new("nine-patches").join(path);
+            let metadata = source.read_to_string(&path.with_extension("meta.json"))?;
+            Ok((load_image(source, &path)?, metadata))
+        },
+        |_asset_mgr, _path, (image, metadata)| {
+            Ok(Arc::new(SpriteNinePatch::from_resource(&image, &metadata)?))
+        },
+    )
 }
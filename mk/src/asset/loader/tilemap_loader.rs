@@ -2,7 +2,7 @@ use crate::asset::{AssetLoadError, AssetLoader};
 use crate::render::*;
 use serde::{Deserialize, Serialize};
 use serde_json::Error as JSONError;
-use std::fs::read_to_string;
+use std::path::Path;
 
 #[derive(Serialize, Deserialize)]
 struct TilemapJSON {
@@ -32,35 +32,41 @@ impl From<JSONError> for AssetLoadError {
 }
 
 pub fn tilemap_loader() -> AssetLoader<Tilemap> {
-    AssetLoader::new(|asset_mgr, base, path| {
-        let tilemap_json: TilemapJSON = serde_json::from_str(&read_to_string(
-            &base.join("maps").join(path).with_extension("json"),
-        )?)?;
+    AssetLoader::new_async(
+        |source, path| {
+            let tilemap_json: TilemapJSON = serde_json::from_str(
+                &source.read_to_string(&Path::new("maps").join(path).with_extension("json"))?,
+            )?;
 
-        if tilemap_json.tilesets.len() != 1 {
-            return Err(AssetLoadError::from(
-                "tilemap must use a single tileset only",
-            ));
-        }
+            if tilemap_json.tilesets.len() != 1 {
+                return Err(AssetLoadError::from(
+                    "tilemap must use a single tileset only",
+                ));
+            }
 
-        if tilemap_json.tilesets[0].firstgid != 1 {
-            return Err(AssetLoadError::from("tileset's firstgid must be 1"));
-        }
+            if tilemap_json.tilesets[0].firstgid != 1 {
+                return Err(AssetLoadError::from("tileset's firstgid must be 1"));
+            }
 
-        let palette = asset_mgr.load(&tilemap_json.tilesets[0].source)?;
+            Ok(tilemap_json)
+        },
+        |asset_mgr, _path, tilemap_json| {
+            // The palette is a GPU asset too, so it is loaded on the main thread.
+            let palette = asset_mgr.load(&tilemap_json.tilesets[0].source)?;
 
-        Ok(Tilemap {
-            tile_width: tilemap_json.tilewidth as f32,
-            tile_height: tilemap_json.tileheight as f32,
-            tile_count_x: tilemap_json.width as usize,
-            tile_count_y: tilemap_json.height as usize,
-            layers: tilemap_json
-                .layers
-                .into_iter()
-                .map(|layer| layer.data)
-                .collect(),
-            palette,
-        }
-        .into())
-    })
+            Ok(Tilemap {
+                tile_width: tilemap_json.tilewidth as f32,
+                tile_height: tilemap_json.tileheight as f32,
+                tile_count_x: tilemap_json.width as usize,
+                tile_count_y: tilemap_json.height as usize,
+                layers: tilemap_json
+                    .layers
+                    .into_iter()
+                    .map(|layer| layer.data)
+                    .collect(),
+                palette,
+            }
+            .into())
+        },
+    )
 }
@@ -1,14 +1,22 @@
 mod asset_cache_manager;
 mod asset_load_error;
+mod asset_load_handle;
 mod asset_loader;
 mod asset_manager;
+mod asset_reload;
+mod asset_source;
+mod asset_worker_pool;
 mod base_asset_cache_manager;
 mod base_asset_loader;
 pub mod loader;
 
 pub use asset_cache_manager::*;
 pub use asset_load_error::*;
+pub use asset_load_handle::*;
 pub use asset_loader::*;
 pub use asset_manager::*;
+pub use asset_reload::*;
+pub use asset_source::*;
+pub use asset_worker_pool::*;
 pub use base_asset_cache_manager::*;
 pub use base_asset_loader::*;
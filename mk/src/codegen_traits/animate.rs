@@ -1,10 +1,12 @@
 pub trait Animate {
     fn ty(&self) -> &'static str;
+    // Returns false if the time line's field is not animatable on this type.
     fn animate(
         &mut self,
         _time_line: &crate::animation::AnimationTimeLine,
         _key_frame: &crate::animation::AnimationKeyFrame,
         _normalized_time_in_key_frame: f32,
-    ) {
+    ) -> bool {
+        false
     }
 }
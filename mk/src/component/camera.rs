@@ -1,8 +1,352 @@
-use crate::render::Layer;
-use codegen::{Animation, LuaComponent};
+use crate::api::{use_context, Entity};
+use crate::component::{Diagnostic, NinePatchRenderer, Size, SpriteRenderer, Transform};
+use crate::render::{Color, Layer, LuaRcRenderTarget, RenderTarget};
+use crate::structure::Vec2;
+use codegen::{Animation, LuaComponent, LuaStruct};
+use legion::{component, EntityStore, IntoQuery};
+use mlua::prelude::*;
+use std::cmp::Reverse;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+const MIN_ZOOM: f32 = 0.0001f32;
+
+// A region of the screen, normalized to [0, 1] and measured from the bottom left.
+#[derive(LuaStruct, Debug, Clone, Copy, PartialEq)]
+pub struct CameraViewport {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for CameraViewport {
+    fn default() -> Self {
+        Self {
+            x: 0f32,
+            y: 0f32,
+            width: 1f32,
+            height: 1f32,
+        }
+    }
+}
 
 #[derive(Animation, LuaComponent, Debug)]
 pub struct Camera {
     pub layer: Layer,
+    #[animate(field = "order", ty = "integer")]
     pub order: isize,
+    pub viewport: CameraViewport,
+    // Magnification applied on top of the transform; 2 shows everything twice as large.
+    #[animate(field = "zoom", ty = "float", set = set_zoom)]
+    #[lua_userfunc(get=lua_get_zoom, set=lua_set_zoom)]
+    pub zoom: f32,
+    // Clears the viewport before rendering; nothing is cleared if this is nil. Cameras built with a
+    // target clear to transparent unless a color is given.
+    pub clear_color: Option<Color>,
+    pub clear_depth: bool,
+    // Renders into the texture of the target instead of the screen; the viewport is relative to it.
+    #[lua_userfunc(get=lua_get_target, set=lua_set_target)]
+    pub target: Option<Arc<RenderTarget>>,
+    #[lua_method]
+    screen_to_world: PhantomData<()>,
+    #[lua_method]
+    world_to_screen: PhantomData<()>,
+    #[lua_method]
+    sprites_at: PhantomData<()>,
+}
+
+impl Camera {
+    pub fn new(layer: Layer, order: isize) -> Self {
+        Self {
+            layer,
+            order,
+            viewport: CameraViewport::default(),
+            zoom: 1f32,
+            clear_color: None,
+            clear_depth: false,
+            target: None,
+            screen_to_world: PhantomData,
+            world_to_screen: PhantomData,
+            sprites_at: PhantomData,
+        }
+    }
+
+    // Animations can overshoot, so the zoom is kept positive instead of being rejected.
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = zoom.max(MIN_ZOOM);
+    }
+
+    // Size of what the camera renders into; the screen's is used if there's no target.
+    pub fn surface_size(&self, screen_width: f32, screen_height: f32) -> (f32, f32) {
+        match &self.target {
+            Some(target) => (target.width() as f32, target.height() as f32),
+            None => (screen_width, screen_height),
+        }
+    }
+
+    // Half of the visible area in world units, before the transform is applied.
+    pub fn half_extents(&self, screen_width: f32, screen_height: f32) -> (f32, f32) {
+        (
+            screen_width * self.viewport.width * 0.5f32 / self.zoom,
+            screen_height * self.viewport.height * 0.5f32 / self.zoom,
+        )
+    }
+
+    // Converts a position in logical pixels from the top left of the screen into the space of the camera.
+    pub fn screen_to_camera(&self, position: Vec2, screen_width: f32, screen_height: f32) -> Vec2 {
+        let center = self.viewport_center(screen_width, screen_height);
+
+        Vec2::new(
+            (position.x - center.x) / self.zoom,
+            (center.y - position.y) / self.zoom,
+        )
+    }
+
+    pub fn camera_to_screen(&self, position: Vec2, screen_width: f32, screen_height: f32) -> Vec2 {
+        let center = self.viewport_center(screen_width, screen_height);
+
+        Vec2::new(
+            center.x + position.x * self.zoom,
+            center.y - position.y * self.zoom,
+        )
+    }
+
+    // The `camera_to_world` is the world matrix of the camera's transform.
+    pub fn screen_to_world(
+        &self,
+        camera_to_world: &[f32; 9],
+        position: Vec2,
+        screen_width: f32,
+        screen_height: f32,
+    ) -> Vec2 {
+        transform_point(
+            camera_to_world,
+            self.screen_to_camera(position, screen_width, screen_height),
+        )
+    }
+
+    // Returns `None` if the camera's transform can't be inverted, e.g. it has a zero scale.
+    pub fn world_to_screen(
+        &self,
+        camera_to_world: &[f32; 9],
+        position: Vec2,
+        screen_width: f32,
+        screen_height: f32,
+    ) -> Option<Vec2> {
+        inverse_transform_point(camera_to_world, position)
+            .map(|position| self.camera_to_screen(position, screen_width, screen_height))
+    }
+
+    pub fn viewport_contains(&self, position: Vec2, screen_width: f32, screen_height: f32) -> bool {
+        let x = position.x / screen_width;
+        let y = 1f32 - position.y / screen_height;
+
+        self.viewport.x <= x
+            && x <= self.viewport.x + self.viewport.width
+            && self.viewport.y <= y
+            && y <= self.viewport.y + self.viewport.height
+    }
+
+    fn viewport_center(&self, screen_width: f32, screen_height: f32) -> Vec2 {
+        Vec2::new(
+            screen_width * (self.viewport.x + self.viewport.width * 0.5f32),
+            screen_height * (1f32 - self.viewport.y - self.viewport.height * 0.5f32),
+        )
+    }
+
+    fn lua_get_zoom<'lua>(&self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        self.zoom.to_lua(lua)
+    }
+
+    fn lua_set_zoom(&mut self, value: LuaValue, lua: &Lua) -> LuaResult<()> {
+        self.zoom = validate_zoom(f32::from_lua(value, lua)?)?;
+        Ok(())
+    }
+
+    fn lua_get_target<'lua>(&self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        self.target.clone().map(LuaRcRenderTarget::from).to_lua(lua)
+    }
+
+    fn lua_set_target(&mut self, value: LuaValue, lua: &Lua) -> LuaResult<()> {
+        self.target = Option::<LuaRcRenderTarget>::from_lua(value, lua)?.map(<_>::from);
+        Ok(())
+    }
+}
+
+impl LuaComponentCamera {
+    fn with_camera<T>(
+        &self,
+        f: impl FnOnce(&legion::World, &Camera, &[f32; 9], f32, f32) -> T,
+    ) -> LuaResult<T> {
+        let context = use_context();
+        let world = context.world();
+        let transform_mgr = context.transform_mgr();
+        let screen_mgr = context.screen_mgr();
+        let entry = match world.entry_ref(self.0) {
+            Ok(entry) => entry,
+            Err(_) => {
+                return Err(format!(
+                    "the type {} used invalid entity id {:?}",
+                    "component:Camera", self.0
+                )
+                .to_lua_err())
+            }
+        };
+        let (transform, camera) = match (
+            entry.get_component::<Transform>(),
+            entry.get_component::<Camera>(),
+        ) {
+            (Ok(transform), Ok(camera)) => (transform, camera),
+            _ => {
+                return Err(format!(
+                    "the entity id {:?} does not contains the type {}",
+                    self.0, "component:Camera"
+                )
+                .to_lua_err())
+            }
+        };
+
+        let (surface_width, surface_height) =
+            camera.surface_size(screen_mgr.width() as f32, screen_mgr.height() as f32);
+
+        Ok(f(
+            &world,
+            camera,
+            transform_mgr.transform_world_matrix(transform.index()),
+            surface_width,
+            surface_height,
+        ))
+    }
+
+    fn screen_to_world(&self, _lua: &Lua, position: Vec2) -> LuaResult<Vec2> {
+        self.with_camera(|_, camera, camera_to_world, screen_width, screen_height| {
+            camera.screen_to_world(camera_to_world, position, screen_width, screen_height)
+        })
+    }
+
+    fn world_to_screen(&self, _lua: &Lua, position: Vec2) -> LuaResult<Option<Vec2>> {
+        self.with_camera(|_, camera, camera_to_world, screen_width, screen_height| {
+            camera.world_to_screen(camera_to_world, position, screen_width, screen_height)
+        })
+    }
+
+    // Entities with a sprite or a nine-patch under the screen position, the topmost one first.
+    fn sprites_at(&self, _lua: &Lua, position: Vec2) -> LuaResult<Vec<Entity>> {
+        self.with_camera(
+            |world, camera, camera_to_world, screen_width, screen_height| {
+                if !camera.viewport_contains(position, screen_width, screen_height) {
+                    return vec![];
+                }
+
+                let transform_mgr = use_context().transform_mgr();
+                let point =
+                    camera.screen_to_world(camera_to_world, position, screen_width, screen_height);
+                let contains = |transform: &Transform, size: &Size| match inverse_transform_point(
+                    transform_mgr.transform_world_matrix(transform.index()),
+                    point,
+                ) {
+                    Some(local) => {
+                        local.x.abs() <= size.width * 0.5f32
+                            && local.y.abs() <= size.height * 0.5f32
+                    }
+                    None => false,
+                };
+                let mut hits = vec![];
+
+                <(legion::Entity, &Transform, &Size, &SpriteRenderer)>::query()
+                    .filter(!component::<Diagnostic>())
+                    .for_each(world, |(entity, transform, size, renderer)| {
+                        if Layer::has_overlap(camera.layer, renderer.layer)
+                            && contains(transform, size)
+                        {
+                            hits.push((renderer.order, *entity));
+                        }
+                    });
+                <(legion::Entity, &Transform, &Size, &NinePatchRenderer)>::query()
+                    .filter(!component::<Diagnostic>())
+                    .for_each(world, |(entity, transform, size, renderer)| {
+                        if Layer::has_overlap(camera.layer, renderer.layer)
+                            && contains(transform, size)
+                        {
+                            hits.push((renderer.order, *entity));
+                        }
+                    });
+
+                hits.sort_by_key(|&(order, _)| Reverse(order));
+                hits.into_iter()
+                    .map(|(_, entity)| Entity::new(entity))
+                    .collect()
+            },
+        )
+    }
+}
+
+// Everything would collapse into a point or be mirrored otherwise.
+pub fn validate_zoom(zoom: f32) -> LuaResult<f32> {
+    if zoom > 0f32 && zoom.is_finite() {
+        Ok(zoom)
+    } else {
+        Err(format!(
+            "camera's zoom must be a positive number, but {} is given",
+            zoom
+        )
+        .to_lua_err())
+    }
+}
+
+fn transform_point(matrix: &[f32; 9], point: Vec2) -> Vec2 {
+    Vec2::new(
+        matrix[0] * point.x + matrix[3] * point.y + matrix[6],
+        matrix[1] * point.x + matrix[4] * point.y + matrix[7],
+    )
+}
+
+fn inverse_transform_point(matrix: &[f32; 9], point: Vec2) -> Option<Vec2> {
+    let det = matrix[0] * matrix[4] - matrix[1] * matrix[3];
+
+    if det == 0f32 {
+        return None;
+    }
+
+    let x = point.x - matrix[6];
+    let y = point.y - matrix[7];
+
+    Some(Vec2::new(
+        (x * matrix[4] - y * matrix[3]) / det,
+        (y * matrix[0] - x * matrix[1]) / det,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn screen_and_world_positions_round_trip() {
+        let mut camera = Camera::new(Layer::default(), 0);
+        camera.viewport = CameraViewport {
+            x: 0.5f32,
+            y: 0f32,
+            width: 0.5f32,
+            height: 1f32,
+        };
+        camera.zoom = 2f32;
+
+        // Rotated by 90 degrees and moved to (10, 20).
+        let camera_to_world = [0f32, 1f32, 0f32, -1f32, 0f32, 0f32, 10f32, 20f32, 1f32];
+
+        // The center of the right half of a 200x100 screen is where the camera is.
+        let world =
+            camera.screen_to_world(&camera_to_world, Vec2::new(150f32, 50f32), 200f32, 100f32);
+        assert_eq!(world, Vec2::new(10f32, 20f32));
+
+        // 20 pixels to the right is 10 units along the camera's x axis, which points up.
+        let world =
+            camera.screen_to_world(&camera_to_world, Vec2::new(170f32, 50f32), 200f32, 100f32);
+        assert_eq!(world, Vec2::new(10f32, 30f32));
+
+        let screen = camera.world_to_screen(&camera_to_world, world, 200f32, 100f32);
+        assert_eq!(screen, Some(Vec2::new(170f32, 50f32)));
+    }
 }
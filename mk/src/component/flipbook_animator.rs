@@ -0,0 +1,364 @@
+use crate::render::{Sprite, SpriteAtlas, SpriteAtlasGrid};
+use codegen::LuaComponent;
+use mlua::prelude::*;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlipbookMode {
+    Loop,
+    PingPong,
+    Once,
+}
+
+impl<'lua> FromLua<'lua> for FlipbookMode {
+    fn from_lua(value: LuaValue<'lua>, lua: &'lua Lua) -> LuaResult<Self> {
+        let str = String::from_lua(value, lua)?;
+        let str = str.as_str();
+        match str {
+            "loop" => Ok(FlipbookMode::Loop),
+            "pingpong" => Ok(FlipbookMode::PingPong),
+            "once" => Ok(FlipbookMode::Once),
+            _ => Err(
+                format!("{:?} is invalid value for the type {}", str, "FlipbookMode",).to_lua_err(),
+            ),
+        }
+    }
+}
+
+impl<'lua> ToLua<'lua> for FlipbookMode {
+    fn to_lua(self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        Ok(LuaValue::String(lua.create_string(match self {
+            FlipbookMode::Loop => "loop",
+            FlipbookMode::PingPong => "pingpong",
+            FlipbookMode::Once => "once",
+        })?))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum FlipbookSource {
+    Grid(Arc<SpriteAtlasGrid>),
+    Atlas(Arc<SpriteAtlas>),
+}
+
+impl FlipbookSource {
+    pub fn sprite(&self, sprite: &FlipbookSprite) -> Option<&Arc<Sprite>> {
+        match (self, sprite) {
+            (Self::Grid(grid), FlipbookSprite::Index(index)) => grid.sprites().get(*index),
+            (Self::Atlas(atlas), FlipbookSprite::Name(name)) => atlas.sprites().get(name),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FlipbookSprite {
+    Index(usize),
+    Name(String),
+}
+
+impl<'lua> FromLua<'lua> for FlipbookSprite {
+    fn from_lua(value: LuaValue<'lua>, lua: &'lua Lua) -> LuaResult<Self> {
+        match value {
+            // Grid sprites are indexed from 1 in Lua, the same as the sequence exposed by the grid.
+            LuaValue::Integer(index) if 1 <= index => Ok(Self::Index(index as usize - 1)),
+            LuaValue::String(..) => Ok(Self::Name(String::from_lua(value, lua)?)),
+            _ => Err(format!(
+                "the type {} must be a positive integer or a string",
+                "FlipbookSprite"
+            )
+            .to_lua_err()),
+        }
+    }
+}
+
+impl<'lua> ToLua<'lua> for FlipbookSprite {
+    fn to_lua(self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        match self {
+            Self::Index(index) => (index + 1).to_lua(lua),
+            Self::Name(name) => name.to_lua(lua),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FlipbookFrame {
+    pub sprite: FlipbookSprite,
+    pub duration: f32,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FlipbookAdvance {
+    pub looped: bool,
+    pub finished: bool,
+}
+
+#[derive(LuaComponent, Debug)]
+pub struct FlipbookAnimator {
+    #[lua_hidden]
+    pub source: FlipbookSource,
+    #[lua_hidden]
+    pub frames: Vec<FlipbookFrame>,
+    pub mode: FlipbookMode,
+    // Flipbooks can't be played backwards, so the speed must not be negative.
+    #[lua_userfunc(get=lua_get_speed, set=lua_set_speed)]
+    pub speed: f32,
+    pub is_playing: bool,
+    #[lua_userfunc(get=lua_get_frame, set=lua_set_frame)]
+    frame: usize,
+    #[lua_hidden]
+    time: f32,
+    #[lua_hidden]
+    is_pong: bool,
+}
+
+impl FlipbookAnimator {
+    pub fn new(source: FlipbookSource, frames: Vec<FlipbookFrame>, mode: FlipbookMode) -> Self {
+        Self {
+            source,
+            frames,
+            mode,
+            speed: 1f32,
+            is_playing: true,
+            frame: 0,
+            time: 0f32,
+            is_pong: false,
+        }
+    }
+
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    pub fn set_frame(&mut self, frame: usize) {
+        self.frame = frame.min(self.frames.len().saturating_sub(1));
+        self.time = 0f32;
+        self.is_pong = false;
+    }
+
+    pub fn sprite(&self) -> Option<&Arc<Sprite>> {
+        self.frames
+            .get(self.frame)
+            .and_then(|frame| self.source.sprite(&frame.sprite))
+    }
+
+    pub fn advance(&mut self, dt: f32) -> FlipbookAdvance {
+        if !self.is_playing || self.frames.is_empty() {
+            return FlipbookAdvance::default();
+        }
+
+        let advance = advance_frames(
+            &self.frames,
+            self.mode,
+            &mut self.frame,
+            &mut self.time,
+            &mut self.is_pong,
+            dt * self.speed,
+        );
+
+        if advance.finished {
+            self.is_playing = false;
+        }
+
+        advance
+    }
+
+    fn lua_get_speed<'lua>(&self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        self.speed.to_lua(lua)
+    }
+
+    fn lua_set_speed(&mut self, value: LuaValue, lua: &Lua) -> LuaResult<()> {
+        self.speed = validate_flipbook_speed(f32::from_lua(value, lua)?)?;
+        Ok(())
+    }
+
+    fn lua_get_frame<'lua>(&self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        (self.frame + 1).to_lua(lua)
+    }
+
+    fn lua_set_frame(&mut self, value: LuaValue, lua: &Lua) -> LuaResult<()> {
+        let frame = usize::from_lua(value, lua)?;
+
+        if frame == 0 || self.frames.len() < frame {
+            return Err(format!(
+                "frame {} is out of range; the flipbook has {} frames",
+                frame,
+                self.frames.len()
+            )
+            .to_lua_err());
+        }
+
+        self.set_frame(frame - 1);
+        Ok(())
+    }
+}
+
+pub fn validate_flipbook_speed(speed: f32) -> LuaResult<f32> {
+    if speed >= 0f32 && speed.is_finite() {
+        Ok(speed)
+    } else {
+        Err(format!(
+            "flipbook's speed must be a non-negative number, but {} is given",
+            speed
+        )
+        .to_lua_err())
+    }
+}
+
+// Advances the playback through the non-empty frames by the given (already scaled) delta.
+pub(crate) fn advance_frames(
+    frames: &[FlipbookFrame],
+    mode: FlipbookMode,
+    frame: &mut usize,
+    time: &mut f32,
+    is_pong: &mut bool,
+    delta: f32,
+) -> FlipbookAdvance {
+    let mut advance = FlipbookAdvance::default();
+    let last = frames.len() - 1;
+
+    *time += delta;
+
+    loop {
+        // Zero-length frames are clamped to keep a huge delta from spinning forever.
+        let duration = frames[*frame].duration.max(1e-3f32);
+
+        if *time < duration {
+            break;
+        }
+
+        *time -= duration;
+
+        match mode {
+            // A single frame has nowhere to turn around, so it just loops.
+            FlipbookMode::Loop | FlipbookMode::PingPong if last == 0 => {
+                advance.looped = true;
+            }
+            FlipbookMode::Loop => {
+                if *frame == last {
+                    *frame = 0;
+                    advance.looped = true;
+                } else {
+                    *frame += 1;
+                }
+            }
+            FlipbookMode::PingPong => {
+                if *is_pong {
+                    if *frame == 0 {
+                        *is_pong = false;
+                        *frame = 1;
+                        advance.looped = true;
+                    } else {
+                        *frame -= 1;
+                    }
+                } else if *frame == last {
+                    *is_pong = true;
+                    *frame = last - 1;
+                } else {
+                    *frame += 1;
+                }
+            }
+            FlipbookMode::Once => {
+                if *frame == last {
+                    *time = 0f32;
+                    advance.finished = true;
+                    break;
+                } else {
+                    *frame += 1;
+                }
+            }
+        }
+    }
+
+    advance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(count: usize) -> Vec<FlipbookFrame> {
+        (0..count)
+            .map(|index| FlipbookFrame {
+                sprite: FlipbookSprite::Index(index),
+                duration: 1f32,
+            })
+            .collect()
+    }
+
+    // Advances by one frame per step and returns the frame shown after each step, with the advance.
+    fn play(count: usize, mode: FlipbookMode, steps: usize) -> Vec<(usize, FlipbookAdvance)> {
+        let frames = frames(count);
+        let (mut frame, mut time, mut is_pong) = (0, 0f32, false);
+
+        (0..steps)
+            .map(|_| {
+                let advance =
+                    advance_frames(&frames, mode, &mut frame, &mut time, &mut is_pong, 1f32);
+                (frame, advance)
+            })
+            .collect()
+    }
+
+    fn shown(advances: &[(usize, FlipbookAdvance)]) -> Vec<usize> {
+        advances.iter().map(|(frame, _)| *frame).collect()
+    }
+
+    fn looped(advances: &[(usize, FlipbookAdvance)]) -> Vec<bool> {
+        advances.iter().map(|(_, advance)| advance.looped).collect()
+    }
+
+    #[test]
+    fn loop_mode_wraps_around_to_the_first_frame() {
+        let advances = play(3, FlipbookMode::Loop, 4);
+
+        assert_eq!(shown(&advances), vec![1, 2, 0, 1]);
+        assert_eq!(looped(&advances), vec![false, false, true, false]);
+    }
+
+    #[test]
+    fn pingpong_mode_turns_around_at_both_ends() {
+        let advances = play(3, FlipbookMode::PingPong, 6);
+
+        assert_eq!(shown(&advances), vec![1, 2, 1, 0, 1, 2]);
+        assert_eq!(
+            looped(&advances),
+            vec![false, false, false, false, true, false]
+        );
+    }
+
+    #[test]
+    fn once_mode_finishes_on_the_last_frame() {
+        let advances = play(3, FlipbookMode::Once, 3);
+
+        assert_eq!(shown(&advances), vec![1, 2, 2]);
+        assert_eq!(
+            advances
+                .iter()
+                .map(|(_, advance)| advance.finished)
+                .collect::<Vec<_>>(),
+            vec![false, false, true]
+        );
+    }
+
+    #[test]
+    fn single_frames_loop_or_finish_after_their_duration() {
+        for mode in [FlipbookMode::Loop, FlipbookMode::PingPong] {
+            let advances = play(1, mode, 2);
+
+            assert_eq!(shown(&advances), vec![0, 0]);
+            assert_eq!(looped(&advances), vec![true, true]);
+        }
+
+        let advances = play(1, FlipbookMode::Once, 1);
+        assert_eq!(shown(&advances), vec![0]);
+        assert!(advances[0].1.finished);
+    }
+
+    #[test]
+    fn negative_speeds_are_rejected() {
+        assert!(validate_flipbook_speed(-1f32).is_err());
+        assert!(validate_flipbook_speed(f32::NAN).is_err());
+        assert_eq!(validate_flipbook_speed(0f32).unwrap(), 0f32);
+    }
+}
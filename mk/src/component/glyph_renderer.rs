@@ -130,7 +130,13 @@ impl<'lua> ToLua<'lua> for GlyphRendererConfig {
 #[derive(Animation, LuaComponent)]
 pub struct GlyphRenderer {
     pub layer: Layer,
+    #[animate(field = "order", ty = "integer")]
     pub order: isize,
+    #[animate(field = "color", ty = "color")]
+    #[animate(field = "color.r", ty = "float", member = "r")]
+    #[animate(field = "color.g", ty = "float", member = "g")]
+    #[animate(field = "color.b", ty = "float", member = "b")]
+    #[animate(field = "color.a", ty = "float", member = "a")]
     pub color: Color,
     #[lua_userdata(LuaRcShader)]
     pub shader: Arc<Shader>,
@@ -139,8 +145,11 @@ pub struct GlyphRenderer {
     // NOTE: Support the userfunc to the animation derive macro too.
     font: Arc<Font>,
     #[lua_userfunc(set=lua_set_font_size)]
+    #[animate(field = "font_size", ty = "float", set = set_font_size)]
     font_size: f32,
+    #[animate(field = "thickness", ty = "float")]
     pub thickness: f32,
+    #[animate(field = "smoothness", ty = "float")]
     pub smoothness: f32,
     #[lua_userfunc(set=lua_set_config)]
     config: GlyphRendererConfig,
@@ -1,11 +1,13 @@
 mod camera;
 mod diagnostic;
+mod flipbook_animator;
 mod glyph_renderer;
 mod nine_patch_renderer;
 mod not_yet_complete;
 mod single_animator;
 mod size;
 mod sprite_renderer;
+mod state_machine_animator;
 mod tilemap_renderer;
 mod transform;
 mod ui_element;
@@ -13,12 +15,14 @@ mod ui_scaler;
 
 pub use camera::*;
 pub use diagnostic::*;
+pub use flipbook_animator::*;
 pub use glyph_renderer::*;
 pub use nine_patch_renderer::*;
 pub use not_yet_complete::*;
 pub use single_animator::*;
 pub use size::*;
 pub use sprite_renderer::*;
+pub use state_machine_animator::*;
 pub use tilemap_renderer::*;
 pub use transform::*;
 pub use ui_element::*;
@@ -5,7 +5,13 @@ use std::sync::Arc;
 #[derive(Animation, LuaComponent)]
 pub struct NinePatchRenderer {
     pub layer: Layer,
+    #[animate(field = "order", ty = "integer")]
     pub order: isize,
+    #[animate(field = "color", ty = "color")]
+    #[animate(field = "color.r", ty = "float", member = "r")]
+    #[animate(field = "color.g", ty = "float", member = "g")]
+    #[animate(field = "color.b", ty = "float", member = "b")]
+    #[animate(field = "color.a", ty = "float", member = "a")]
     pub color: Color,
     #[lua_userdata(LuaRcShader)]
     pub shader: Arc<Shader>,
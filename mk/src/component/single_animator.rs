@@ -1,9 +1,25 @@
 use crate::animation::Animation;
+use std::collections::HashSet;
+use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub struct SingleAnimator {
     pub is_pong: bool,
     pub time: f32,
     pub speed: f32,
-    pub animation: Option<Animation>,
+    pub animation: Option<Arc<Animation>>,
+    // Indices of the time lines that target unknown components or fields; they are reported once and skipped.
+    pub invalid_time_lines: HashSet<usize>,
+}
+
+impl SingleAnimator {
+    pub fn new(animation: Arc<Animation>, speed: f32) -> Self {
+        Self {
+            is_pong: false,
+            time: 0f32,
+            speed,
+            animation: Some(animation),
+            invalid_time_lines: HashSet::new(),
+        }
+    }
 }
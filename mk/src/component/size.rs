@@ -1,10 +1,12 @@
-use codegen::LuaComponentNoWrapper;
+use codegen::{Animation, LuaComponentNoWrapper};
 
-#[derive(LuaComponentNoWrapper, Debug, Clone, Copy, PartialEq)]
+#[derive(Animation, LuaComponentNoWrapper, Debug, Clone, Copy, PartialEq)]
 pub struct Size {
     #[lua_hidden]
     index: u32,
+    #[animate(field = "width", ty = "float")]
     pub width: f32,
+    #[animate(field = "height", ty = "float")]
     pub height: f32,
 }
 
@@ -0,0 +1,480 @@
+use crate::animation::Animation;
+use crate::api::use_context;
+use codegen::LuaComponent;
+use mlua::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnimatorParameter {
+    Bool(bool),
+    Float(f32),
+    // A bool that is reset once a transition consumes it.
+    Trigger(bool),
+}
+
+impl<'lua> FromLua<'lua> for AnimatorParameter {
+    fn from_lua(value: LuaValue<'lua>, lua: &'lua Lua) -> LuaResult<Self> {
+        match value {
+            LuaValue::Boolean(value) => Ok(Self::Bool(value)),
+            LuaValue::Integer(..) | LuaValue::Number(..) => {
+                Ok(Self::Float(f32::from_lua(value, lua)?))
+            }
+            LuaValue::String(str) if str.as_bytes() == b"trigger" => Ok(Self::Trigger(false)),
+            _ => Err(format!(
+                "the type {} must be a boolean, a number or \"trigger\"",
+                "AnimatorParameter"
+            )
+            .to_lua_err()),
+        }
+    }
+}
+
+impl<'lua> ToLua<'lua> for AnimatorParameter {
+    fn to_lua(self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        match self {
+            Self::Bool(value) | Self::Trigger(value) => value.to_lua(lua),
+            Self::Float(value) => value.to_lua(lua),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnimatorConditionOp {
+    Equal,
+    NotEqual,
+    Greater,
+    Less,
+    GreaterEqual,
+    LessEqual,
+}
+
+impl<'lua> FromLua<'lua> for AnimatorConditionOp {
+    fn from_lua(value: LuaValue<'lua>, lua: &'lua Lua) -> LuaResult<Self> {
+        let str = String::from_lua(value, lua)?;
+        let str = str.as_str();
+        match str {
+            "==" => Ok(AnimatorConditionOp::Equal),
+            "~=" => Ok(AnimatorConditionOp::NotEqual),
+            ">" => Ok(AnimatorConditionOp::Greater),
+            "<" => Ok(AnimatorConditionOp::Less),
+            ">=" => Ok(AnimatorConditionOp::GreaterEqual),
+            "<=" => Ok(AnimatorConditionOp::LessEqual),
+            _ => Err(format!(
+                "{:?} is invalid value for the type {}",
+                str, "AnimatorConditionOp",
+            )
+            .to_lua_err()),
+        }
+    }
+}
+
+impl<'lua> ToLua<'lua> for AnimatorConditionOp {
+    fn to_lua(self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        Ok(LuaValue::String(lua.create_string(match self {
+            AnimatorConditionOp::Equal => "==",
+            AnimatorConditionOp::NotEqual => "~=",
+            AnimatorConditionOp::Greater => ">",
+            AnimatorConditionOp::Less => "<",
+            AnimatorConditionOp::GreaterEqual => ">=",
+            AnimatorConditionOp::LessEqual => "<=",
+        })?))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimatorCondition {
+    pub parameter: String,
+    // Without a comparison, the condition holds while the parameter is true, set or non-zero.
+    pub comparison: Option<(AnimatorConditionOp, AnimatorParameter)>,
+}
+
+impl AnimatorCondition {
+    pub fn is_met(&self, parameter: AnimatorParameter) -> bool {
+        match (self.comparison, parameter) {
+            (None, AnimatorParameter::Bool(value) | AnimatorParameter::Trigger(value)) => value,
+            (None, AnimatorParameter::Float(value)) => value != 0f32,
+            (Some((op, AnimatorParameter::Float(rhs))), AnimatorParameter::Float(lhs)) => {
+                match op {
+                    AnimatorConditionOp::Equal => lhs == rhs,
+                    AnimatorConditionOp::NotEqual => lhs != rhs,
+                    AnimatorConditionOp::Greater => lhs > rhs,
+                    AnimatorConditionOp::Less => lhs < rhs,
+                    AnimatorConditionOp::GreaterEqual => lhs >= rhs,
+                    AnimatorConditionOp::LessEqual => lhs <= rhs,
+                }
+            }
+            (
+                Some((AnimatorConditionOp::Equal, AnimatorParameter::Bool(rhs))),
+                AnimatorParameter::Bool(lhs),
+            ) => lhs == rhs,
+            (
+                Some((AnimatorConditionOp::NotEqual, AnimatorParameter::Bool(rhs))),
+                AnimatorParameter::Bool(lhs),
+            ) => lhs != rhs,
+            _ => false,
+        }
+    }
+
+    // Returns false if the comparison can never be evaluated against the parameter.
+    pub fn is_compatible(&self, parameter: AnimatorParameter) -> bool {
+        matches!(
+            (self.comparison, parameter),
+            (None, _)
+                | (
+                    Some((_, AnimatorParameter::Float(..))),
+                    AnimatorParameter::Float(..)
+                )
+                | (
+                    Some((
+                        AnimatorConditionOp::Equal | AnimatorConditionOp::NotEqual,
+                        AnimatorParameter::Bool(..),
+                    )),
+                    AnimatorParameter::Bool(..),
+                )
+        )
+    }
+}
+
+impl<'lua> FromLua<'lua> for AnimatorCondition {
+    fn from_lua(value: LuaValue<'lua>, lua: &'lua Lua) -> LuaResult<Self> {
+        let table = LuaTable::from_lua(value, lua)?;
+        let parameter = table.get::<_, String>("parameter")?;
+        let op = table.get::<_, Option<AnimatorConditionOp>>("op")?;
+        let value = match table.get::<_, LuaValue>("value")? {
+            LuaValue::Nil => None,
+            value @ LuaValue::Boolean(..) => Some(AnimatorParameter::from_lua(value, lua)?),
+            value @ (LuaValue::Integer(..) | LuaValue::Number(..)) => {
+                Some(AnimatorParameter::from_lua(value, lua)?)
+            }
+            _ => {
+                return Err(format!(
+                    "the value of the condition on '{}' must be a boolean or a number",
+                    parameter
+                )
+                .to_lua_err())
+            }
+        };
+        let comparison = match (op, value) {
+            (Some(op), Some(value)) => Some((op, value)),
+            (None, None) => None,
+            _ => {
+                return Err(format!(
+                    "the condition on '{}' requires both op and value, or neither",
+                    parameter
+                )
+                .to_lua_err())
+            }
+        };
+
+        Ok(Self {
+            parameter,
+            comparison,
+        })
+    }
+}
+
+impl<'lua> ToLua<'lua> for AnimatorCondition {
+    fn to_lua(self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        let table = lua.create_table()?;
+        table.set("parameter", self.parameter)?;
+
+        if let Some((op, value)) = self.comparison {
+            table.set("op", op)?;
+            table.set("value", value)?;
+        }
+
+        table.to_lua(lua)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AnimatorState {
+    pub animation: Arc<Animation>,
+    pub speed: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct AnimatorTransition {
+    // Transitions without a source state can be taken from any other state.
+    pub from: Option<String>,
+    pub to: String,
+    pub conditions: Vec<AnimatorCondition>,
+    // The crossfade duration in seconds; zero switches instantly.
+    pub duration: f32,
+    // The number of normalized cycles the source state has to play before leaving.
+    pub exit_time: Option<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimatorPlayback {
+    pub state: String,
+    pub time: f32,
+    pub is_pong: bool,
+    // The normalized time played since entering the state, not wrapped by loops.
+    pub progress: f32,
+    // Non-looping animations hold their last pose once finished.
+    pub finished: bool,
+}
+
+impl AnimatorPlayback {
+    pub fn new(state: impl Into<String>) -> Self {
+        Self {
+            state: state.into(),
+            time: 0f32,
+            is_pong: false,
+            progress: 0f32,
+            finished: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AnimatorCrossfade {
+    // The playback of the state being faded out.
+    pub playback: AnimatorPlayback,
+    pub elapsed: f32,
+    pub duration: f32,
+}
+
+impl AnimatorCrossfade {
+    pub fn weight(&self) -> f32 {
+        (self.elapsed / self.duration).clamp(0f32, 1f32)
+    }
+}
+
+#[derive(LuaComponent, Debug)]
+pub struct StateMachineAnimator {
+    #[lua_hidden]
+    pub states: HashMap<String, AnimatorState>,
+    #[lua_hidden]
+    pub transitions: Vec<AnimatorTransition>,
+    #[lua_hidden]
+    parameters: HashMap<String, AnimatorParameter>,
+    #[lua_hidden]
+    pub current: AnimatorPlayback,
+    #[lua_hidden]
+    pub crossfade: Option<AnimatorCrossfade>,
+    // The indices of the time lines that target unknown components or fields, by state name.
+    #[lua_hidden]
+    pub invalid_time_lines: HashMap<String, HashSet<usize>>,
+    // The state changes (from, to) that are not notified yet.
+    #[lua_hidden]
+    pub state_changes: Vec<(String, String)>,
+    pub speed: f32,
+    #[lua_readonly]
+    #[lua_userfunc(get=lua_get_state)]
+    state: PhantomData<String>,
+    #[lua_readonly]
+    #[lua_userfunc(get=lua_get_is_transitioning)]
+    is_transitioning: PhantomData<bool>,
+    #[lua_method]
+    get: PhantomData<()>,
+    #[lua_method]
+    set_bool: PhantomData<()>,
+    #[lua_method]
+    set_float: PhantomData<()>,
+    #[lua_method]
+    set_trigger: PhantomData<()>,
+    #[lua_method]
+    reset_trigger: PhantomData<()>,
+    #[lua_method]
+    play: PhantomData<()>,
+}
+
+impl StateMachineAnimator {
+    pub fn new(
+        states: HashMap<String, AnimatorState>,
+        transitions: Vec<AnimatorTransition>,
+        parameters: HashMap<String, AnimatorParameter>,
+        initial_state: impl Into<String>,
+    ) -> Self {
+        Self {
+            states,
+            transitions,
+            parameters,
+            current: AnimatorPlayback::new(initial_state),
+            crossfade: None,
+            invalid_time_lines: HashMap::new(),
+            state_changes: Vec::new(),
+            speed: 1f32,
+            state: PhantomData,
+            is_transitioning: PhantomData,
+            get: PhantomData,
+            set_bool: PhantomData,
+            set_float: PhantomData,
+            set_trigger: PhantomData,
+            reset_trigger: PhantomData,
+            play: PhantomData,
+        }
+    }
+
+    pub fn state(&self) -> &str {
+        &self.current.state
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        self.crossfade.is_some()
+    }
+
+    pub fn parameter(&self, name: &str) -> Option<AnimatorParameter> {
+        self.parameters.get(name).copied()
+    }
+
+    pub fn parameters(&self) -> &HashMap<String, AnimatorParameter> {
+        &self.parameters
+    }
+
+    // Fails if the parameter doesn't exist or its kind differs from the value.
+    pub fn set_parameter(&mut self, name: &str, value: AnimatorParameter) -> Result<(), String> {
+        match (self.parameters.get_mut(name), value) {
+            (Some(parameter @ AnimatorParameter::Bool(..)), AnimatorParameter::Bool(..))
+            | (Some(parameter @ AnimatorParameter::Float(..)), AnimatorParameter::Float(..))
+            | (Some(parameter @ AnimatorParameter::Trigger(..)), AnimatorParameter::Trigger(..)) => {
+                *parameter = value;
+                Ok(())
+            }
+            (Some(parameter), _) => Err(format!(
+                "the parameter '{}' is {:?}, which is not compatible with {:?}",
+                name, parameter, value
+            )),
+            (None, _) => Err(format!("the parameter '{}' does not exist", name)),
+        }
+    }
+
+    // Switches to the state, crossfading from the current one for the given seconds.
+    pub fn play(&mut self, state: impl Into<String>, duration: f32) {
+        let previous = std::mem::replace(&mut self.current, AnimatorPlayback::new(state));
+
+        self.state_changes
+            .push((previous.state.clone(), self.current.state.clone()));
+        self.crossfade = if 0f32 < duration {
+            Some(AnimatorCrossfade {
+                playback: previous,
+                elapsed: 0f32,
+                duration,
+            })
+        } else {
+            None
+        };
+    }
+
+    // Takes the first transition, in the order they are defined, whose conditions are all met.
+    pub fn evaluate_transitions(&mut self) {
+        let current = &self.current;
+        let parameters = &self.parameters;
+        let transition = self
+            .transitions
+            .iter()
+            .find(|transition| {
+                let is_from_current = match &transition.from {
+                    Some(from) => from == &current.state,
+                    None => transition.to != current.state,
+                };
+
+                is_from_current
+                    && transition
+                        .exit_time
+                        .is_none_or(|exit_time| exit_time <= current.progress)
+                    && transition.conditions.iter().all(|condition| {
+                        parameters
+                            .get(&condition.parameter)
+                            .is_some_and(|&parameter| condition.is_met(parameter))
+                    })
+            })
+            .cloned();
+
+        let transition = if let Some(transition) = transition {
+            transition
+        } else {
+            return;
+        };
+
+        for condition in &transition.conditions {
+            if let Some(AnimatorParameter::Trigger(value)) =
+                self.parameters.get_mut(&condition.parameter)
+            {
+                *value = false;
+            }
+        }
+
+        self.play(transition.to, transition.duration);
+    }
+
+    fn lua_get_state<'lua>(&self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        self.current.state.as_str().to_lua(lua)
+    }
+
+    fn lua_get_is_transitioning<'lua>(&self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        self.is_transitioning().to_lua(lua)
+    }
+}
+
+impl LuaComponentStateMachineAnimator {
+    fn with_animator<T>(
+        &self,
+        f: impl FnOnce(&mut StateMachineAnimator) -> Result<T, String>,
+    ) -> LuaResult<T> {
+        let mut world = use_context().world_mut();
+        let mut entry = match world.entry(self.0) {
+            Some(entry) => entry,
+            None => {
+                return Err(format!(
+                    "the type {} used invalid entity id {:?}",
+                    "component:StateMachineAnimator", self.0
+                )
+                .to_lua_err())
+            }
+        };
+        let animator = match entry.get_component_mut::<StateMachineAnimator>() {
+            Ok(animator) => animator,
+            Err(_) => {
+                return Err(format!(
+                    "the entity id {:?} does not contains the type {}",
+                    self.0, "component:StateMachineAnimator"
+                )
+                .to_lua_err())
+            }
+        };
+
+        f(animator).map_err(|err| err.to_lua_err())
+    }
+
+    fn get(&self, _lua: &Lua, name: String) -> LuaResult<Option<AnimatorParameter>> {
+        self.with_animator(|animator| Ok(animator.parameter(&name)))
+    }
+
+    fn set_bool(&self, _lua: &Lua, (name, value): (String, bool)) -> LuaResult<()> {
+        self.with_animator(|animator| animator.set_parameter(&name, AnimatorParameter::Bool(value)))
+    }
+
+    fn set_float(&self, _lua: &Lua, (name, value): (String, f32)) -> LuaResult<()> {
+        self.with_animator(|animator| {
+            animator.set_parameter(&name, AnimatorParameter::Float(value))
+        })
+    }
+
+    fn set_trigger(&self, _lua: &Lua, name: String) -> LuaResult<()> {
+        self.with_animator(|animator| {
+            animator.set_parameter(&name, AnimatorParameter::Trigger(true))
+        })
+    }
+
+    fn reset_trigger(&self, _lua: &Lua, name: String) -> LuaResult<()> {
+        self.with_animator(|animator| {
+            animator.set_parameter(&name, AnimatorParameter::Trigger(false))
+        })
+    }
+
+    fn play(&self, _lua: &Lua, (state, duration): (String, Option<f32>)) -> LuaResult<()> {
+        self.with_animator(|animator| {
+            if !animator.states.contains_key(&state) {
+                return Err(format!("the state '{}' does not exist", state));
+            }
+
+            animator.play(state, duration.unwrap_or(0f32));
+            Ok(())
+        })
+    }
+}
@@ -46,6 +46,9 @@ impl<'lua> ToLua<'lua> for UIScaleMode {
 #[derive(Animation, LuaComponent)]
 pub struct UIScaler {
     pub mode: UIScaleMode,
+    #[animate(field = "reference_size", ty = "size")]
+    #[animate(field = "reference_size.width", ty = "float", member = "width")]
+    #[animate(field = "reference_size.height", ty = "float", member = "height")]
     pub reference_size: Size,
 }
 
@@ -1,28 +1,36 @@
-use crate::api::use_context;
+use crate::api::{use_context, LuaScriptPath};
 use crate::asset::*;
 use crate::event::*;
+use crate::input::{
+    default_clipboard, default_gamepad_backend, GamepadBackend, PointerButton,
+    VirtualGamepadBackend,
+};
 use crate::render::*;
 use crate::system::*;
+use crate::ui::UIEventManager;
 use crate::util::*;
 use crate::{emit_diagnostic_error, emit_diagnostic_info};
 use crate::{EngineContext, EngineContextWithoutSystemManager, EngineError};
 #[cfg(debug_assertions)]
 use colored::*;
 use glutin::dpi::LogicalSize;
-use glutin::event::{ElementState, Event, MouseButton, WindowEvent};
+use glutin::event::{ElementState, Event, MouseScrollDelta, WindowEvent};
 use glutin::event_loop::{ControlFlow, EventLoop};
 use glutin::window::WindowBuilder;
 use glutin::{ContextBuilder, GlProfile};
-use std::fs::read_to_string;
-use std::path::{Path, PathBuf};
+use mlua::ToLuaMulti;
+use std::mem::take;
+use std::path::Path;
+use std::sync::Arc;
 
 pub fn run(
     title: &str,
     width: u32,
     height: u32,
     resizable: bool,
-    asset_base: impl Into<PathBuf>,
+    asset_source: impl 'static + AssetSource,
     entry_script_path: impl AsRef<Path>,
+    dev_mode: bool,
 ) -> Result<(), EngineError> {
     let event_loop = EventLoop::new();
     let gfx_context = ContextBuilder::new()
@@ -41,14 +49,299 @@ pub fn run(
 
     init(|s| gfx_context.context().get_proc_address(s));
 
-    let context = EngineContext::new(width, height, asset_base.into())?;
+    let context = EngineContext::new(width, height, Box::new(asset_source))?;
     let (mut system_mgr, rest) = context.into_split();
 
+    init_context(&rest)?;
+    rest.set_clipboard(default_clipboard());
+    register_systems(&mut system_mgr, true, default_gamepad_backend());
+    register_asset_loaders(&rest, true);
+
+    // Assets and Lua modules are reloaded whenever their files change, and errors in the modules don't stop
+    // the engine.
+    rest.asset_mgr_mut().set_hot_reload(dev_mode);
+    rest.lua_mgr().set_hot_reload(dev_mode);
+
+    {
+        emit_diagnostic_info!(format!("abjusting scale factor."));
+
+        let scale_factor = gfx_context.window().scale_factor();
+        rest.screen_mgr_mut().update_scale_factor(
+            scale_factor,
+            &LogicalSize::new(width, height).to_physical(scale_factor),
+        );
+    }
+
+    execute_entry_script(&rest, entry_script_path.as_ref())?;
+
+    emit_diagnostic_info!(format!("engine is up and running."));
+
+    {
+        let screen_mgr = rest.screen_mgr();
+        resize(
+            screen_mgr.physical_width() as u32,
+            screen_mgr.physical_height() as u32,
+        );
+    }
+    clear();
+    gfx_context.swap_buffers().unwrap();
+    gfx_context.window().set_visible(true);
+
+    let window_id = gfx_context.window().id();
+
+    event_loop.run(move |event, _, control_flow| {
+        *control_flow = ControlFlow::Poll;
+
+        system_mgr.run(&rest, isize::MIN, -1);
+
+        match event {
+            Event::MainEventsCleared => {}
+            Event::WindowEvent {
+                event: WindowEvent::KeyboardInput { input, .. },
+                window_id: id,
+            } if id == window_id => {
+                rest.input_mgr_mut().handle_event(&input);
+
+                if let Some(key) = input.virtual_keycode {
+                    let modifiers = rest.input_mgr().modifiers();
+
+                    match input.state {
+                        ElementState::Pressed => {
+                            rest.event_mgr().dispatcher().emit(
+                                rest.lua_mgr().lua(),
+                                &events::KeyDown::from_key(key, modifiers),
+                            );
+                        }
+                        ElementState::Released => {
+                            rest.event_mgr().dispatcher().emit(
+                                rest.lua_mgr().lua(),
+                                &events::KeyUp::from_key(key, modifiers),
+                            );
+                        }
+                    }
+                }
+
+                return;
+            }
+            Event::WindowEvent {
+                event: WindowEvent::ModifiersChanged(modifiers),
+                window_id: id,
+            } if id == window_id => {
+                rest.input_mgr_mut().handle_modifiers(modifiers);
+                return;
+            }
+            Event::WindowEvent {
+                event: WindowEvent::ReceivedCharacter(character),
+                window_id: id,
+            } if id == window_id => {
+                // Control characters such as backspace are handled through the key events.
+                if character.is_control() {
+                    return;
+                }
+
+                let text = character.to_string();
+
+                rest.event_mgr().dispatcher().emit(
+                    rest.lua_mgr().lua(),
+                    &events::TextInput { text: text.clone() },
+                );
+                UIEventManager::handle_text_input(&text);
+
+                return;
+            }
+            Event::WindowEvent {
+                event: WindowEvent::CursorEntered { .. },
+                window_id: id,
+            } if id == window_id => {
+                rest.event_mgr()
+                    .dispatcher()
+                    .emit(rest.lua_mgr().lua(), &events::PointerEnter);
+
+                return;
+            }
+            Event::WindowEvent {
+                event: WindowEvent::CursorLeft { .. },
+                window_id: id,
+            } if id == window_id => {
+                rest.event_mgr()
+                    .dispatcher()
+                    .emit(rest.lua_mgr().lua(), &events::PointerExit);
+                rest.ui_event_mgr_mut().handle_mouse_exit();
+
+                return;
+            }
+            Event::WindowEvent {
+                event: WindowEvent::CursorMoved { position, .. },
+                window_id: id,
+            } if id == window_id => {
+                let position = position.to_logical(rest.screen_mgr().scale_factor());
+
+                rest.event_mgr().dispatcher().emit(
+                    rest.lua_mgr().lua(),
+                    &events::PointerMove {
+                        pointer_x: position.x,
+                        pointer_y: position.y,
+                    },
+                );
+                rest.ui_event_mgr_mut()
+                    .handle_mouse_move(position.x as f32, position.y as f32);
+
+                return;
+            }
+            Event::WindowEvent {
+                event: WindowEvent::MouseInput { button, state, .. },
+                window_id: id,
+            } if id == window_id => {
+                rest.input_mgr_mut().handle_mouse_button(button, state);
+
+                let button_name = PointerButton::from(button).name();
+                let modifiers = rest.input_mgr().modifiers();
+
+                match state {
+                    ElementState::Pressed => {
+                        rest.event_mgr().dispatcher().emit(
+                            rest.lua_mgr().lua(),
+                            &events::PointerDown {
+                                button: button_name,
+                                modifiers,
+                            },
+                        );
+                        rest.ui_event_mgr_mut().handle_mouse_button_down(button);
+                    }
+                    ElementState::Released => {
+                        rest.event_mgr().dispatcher().emit(
+                            rest.lua_mgr().lua(),
+                            &events::PointerUp {
+                                button: button_name,
+                                modifiers,
+                            },
+                        );
+                        rest.ui_event_mgr_mut().handle_mouse_button_up(button);
+                    }
+                }
+
+                return;
+            }
+            Event::WindowEvent {
+                event: WindowEvent::MouseWheel { delta, .. },
+                window_id: id,
+            } if id == window_id => {
+                let (delta_x, delta_y, unit) = match delta {
+                    MouseScrollDelta::LineDelta(x, y) => (x, y, "line"),
+                    MouseScrollDelta::PixelDelta(position) => {
+                        let position = position.to_logical::<f32>(rest.screen_mgr().scale_factor());
+                        (position.x, position.y, "pixel")
+                    }
+                };
+
+                let modifiers = rest.input_mgr().modifiers();
+
+                rest.event_mgr().dispatcher().emit(
+                    rest.lua_mgr().lua(),
+                    &events::PointerScroll {
+                        delta_x,
+                        delta_y,
+                        unit,
+                        modifiers,
+                    },
+                );
+                UIEventManager::handle_mouse_scroll(delta_x, delta_y, unit);
+
+                return;
+            }
+            Event::WindowEvent {
+                event: WindowEvent::Focused(false),
+                window_id: id,
+            } if id == window_id => {
+                rest.input_mgr_mut().reset();
+                return;
+            }
+            Event::WindowEvent {
+                event: WindowEvent::Resized(inner_size),
+                window_id: id,
+            } if id == window_id => {
+                rest.screen_mgr_mut().update_size(&inner_size);
+                gfx_context.resize(inner_size);
+                return;
+            }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::ScaleFactorChanged {
+                        scale_factor,
+                        new_inner_size,
+                    },
+                window_id: id,
+            } if id == window_id => {
+                rest.screen_mgr_mut()
+                    .update_scale_factor(scale_factor, new_inner_size);
+                gfx_context.resize(*new_inner_size);
+                return;
+            }
+            Event::WindowEvent {
+                event: WindowEvent::CloseRequested,
+                window_id: id,
+            } if id == window_id => {
+                *control_flow = ControlFlow::Exit;
+                return;
+            }
+            _ => return,
+        }
+
+        {
+            let screen_mgr = rest.screen_mgr();
+            resize(
+                screen_mgr.physical_width() as u32,
+                screen_mgr.physical_height() as u32,
+            );
+        }
+        clear();
+        system_mgr.run(&rest, 0, isize::MAX);
+
+        gfx_context.swap_buffers().unwrap();
+    });
+}
+
+pub fn run_headless(
+    width: u32,
+    height: u32,
+    asset_source: impl 'static + AssetSource,
+    entry_script_path: impl AsRef<Path>,
+    frame_count: usize,
+    dt: f32,
+) -> Result<(), EngineError> {
+    let context = EngineContext::new(width, height, Box::new(asset_source))?;
+    let (mut system_mgr, rest) = context.into_split();
+
+    init_context(&rest)?;
+
+    let gamepad_backend = VirtualGamepadBackend::new();
+    rest.set_virtual_gamepads(Some(gamepad_backend.gamepads()));
+    register_systems(&mut system_mgr, false, Box::new(gamepad_backend));
+    register_asset_loaders(&rest, false);
+
+    rest.time_mgr_mut().set_fixed_dt(Some(dt));
+
+    execute_entry_script(&rest, entry_script_path.as_ref())?;
+
+    emit_diagnostic_info!(format!(
+        "engine is up and running in headless mode for {} frames.",
+        frame_count
+    ));
+
+    for _ in 0..frame_count {
+        system_mgr.run(&rest, isize::MIN, -1);
+        system_mgr.run(&rest, 0, isize::MAX);
+    }
+
+    Ok(())
+}
+
+fn init_context(rest: &Arc<EngineContextWithoutSystemManager>) -> Result<(), EngineError> {
     rest.event_mgr()
         .dispatcher()
         .add_listener(TypedEventListener::Native(BoxId::from_box(Box::new(
             |event: &events::PerEntity| {
-                if let Err(err) = use_context().entity_event_mgr().emit(use_context().lua_mgr().lua(), event) {
+                if let Err(err) = EntityEventManager::emit(use_context().lua_mgr().lua(), event) {
                     emit_diagnostic_error!(format!(
                         "an error occurred while handing entity event {{entity={:?}; event={}}}: {}",
                         event.entity, event.event, err
@@ -137,11 +430,68 @@ pub fn run(
             ))));
     }
 
+    Ok(())
+}
+
+fn execute_entry_script(
+    rest: &Arc<EngineContextWithoutSystemManager>,
+    path: &Path,
+) -> Result<(), EngineError> {
+    emit_diagnostic_info!(format!("executing entry script."));
+
+    let name = path.to_string_lossy().into_owned();
+
+    match rest
+        .lua_mgr()
+        .execute_module(&name, LuaScriptPath::File(path.to_path_buf()))
+    {
+        Ok(..) => Ok(()),
+        // The script is being watched, so keep running until it is fixed.
+        Err(err) if rest.lua_mgr().hot_reload() => {
+            emit_diagnostic_error!(format!(
+                "an error occurred while executing entry script: {}",
+                err
+            ));
+            Ok(())
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn register_systems(
+    system_mgr: &mut SystemManager,
+    render: bool,
+    gamepad_backend: Box<dyn GamepadBackend>,
+) {
     emit_diagnostic_info!(format!("registering built-in systems."));
 
     system_mgr.register_system(isize::MIN, |context: &EngineContextWithoutSystemManager| {
         context.time_mgr_mut().update();
     });
+    system_mgr.register_system(-11200, |context: &EngineContextWithoutSystemManager| {
+        context.lua_mgr().poll_hot_reload();
+    });
+    system_mgr.register_system(-11100, |context: &EngineContextWithoutSystemManager| {
+        let handles = context.asset_mgr().poll_async();
+        let reloads = context.asset_mgr().poll_hot_reload();
+
+        replace_reloaded_assets(&mut context.world_mut(), &reloads);
+
+        for handle in handles {
+            context
+                .event_mgr()
+                .dispatcher()
+                .emit(context.lua_mgr().lua(), &events::AssetLoaded { handle });
+        }
+
+        for reload in reloads {
+            context
+                .event_mgr()
+                .dispatcher()
+                .emit(context.lua_mgr().lua(), &events::AssetReloaded { reload });
+        }
+    });
+    system_mgr.register_system(-11050, GamepadSystem::new(gamepad_backend));
     system_mgr.register_system(-11000, |context: &EngineContextWithoutSystemManager| {
         context.event_mgr().dispatcher().emit(
             context.lua_mgr().lua(),
@@ -151,11 +501,126 @@ pub fn run(
         );
     });
     system_mgr.register_system(-10900, |context: &EngineContextWithoutSystemManager| {
-        animate_sigle_animations(
+        let mut notifications = animate_sigle_animations(
+            &mut context.world_mut(),
+            &context.time_mgr(),
+            &mut context.transform_mgr_mut(),
+            &mut context.ui_mgr_mut(),
+        );
+        notifications.extend(animate_state_machines(
+            &mut context.world_mut(),
+            &context.time_mgr(),
+            &mut context.transform_mgr_mut(),
+            &mut context.ui_mgr_mut(),
+        ));
+
+        for (entity, notification) in notifications {
+            let lua = context.lua_mgr().lua();
+            let (event, param) = match notification {
+                AnimationNotification::Event(event) => (
+                    "animation-event",
+                    (event.name, event.payload).to_lua_multi(lua),
+                ),
+                AnimationNotification::Loop => ("animation-loop", Ok(mlua::MultiValue::new())),
+                AnimationNotification::Finish => ("animation-finish", Ok(mlua::MultiValue::new())),
+                AnimationNotification::StateChange { from, to } => {
+                    ("animation-state", (to, from).to_lua_multi(lua))
+                }
+            };
+
+            if let Err(err) = param.and_then(|param| {
+                EntityEventManager::emit(
+                    lua,
+                    &events::PerEntity {
+                        entity,
+                        event: event.to_owned(),
+                        param,
+                    },
+                )
+            }) {
+                emit_diagnostic_error!(format!(
+                    "an error occurred while emitting event '{}': {}",
+                    event, err
+                ));
+            }
+        }
+
+        let (completions, cancellations) = animate_tweens(
             &mut context.world_mut(),
             &context.time_mgr(),
             &mut context.transform_mgr_mut(),
+            &mut context.ui_mgr_mut(),
+            &mut context.tween_mgr_mut(),
         );
+
+        for tween in cancellations {
+            if let Some(on_complete) = tween.on_complete {
+                context
+                    .lua_mgr()
+                    .lua()
+                    .remove_registry_value(on_complete)
+                    .ok();
+            }
+        }
+
+        for (id, tween) in completions {
+            let lua = context.lua_mgr().lua();
+            let entity = tween.entity;
+
+            if let Some(on_complete) = tween.on_complete {
+                if let Err(err) = lua
+                    .registry_value::<mlua::Function>(&on_complete)
+                    .and_then(|on_complete| on_complete.call::<_, ()>(id))
+                {
+                    emit_diagnostic_error!(format!(
+                        "an error occurred while calling tween's completion callback: {}",
+                        err
+                    ));
+                }
+
+                lua.remove_registry_value(on_complete).ok();
+            }
+
+            if let Err(err) = id.to_lua_multi(lua).and_then(|param| {
+                EntityEventManager::emit(
+                    lua,
+                    &events::PerEntity {
+                        entity,
+                        event: "tween-finish".to_owned(),
+                        param,
+                    },
+                )
+            }) {
+                emit_diagnostic_error!(format!(
+                    "an error occurred while emitting event '{}': {}",
+                    "tween-finish", err
+                ));
+            }
+        }
+
+        let advances = animate_flipbooks(&mut context.world_mut(), &context.time_mgr());
+
+        for (entity, advance) in advances {
+            let event = if advance.finished {
+                "flipbook-finish"
+            } else {
+                "flipbook-loop"
+            };
+
+            if let Err(err) = EntityEventManager::emit(
+                context.lua_mgr().lua(),
+                &events::PerEntity {
+                    entity,
+                    event: event.to_owned(),
+                    param: mlua::MultiValue::new(),
+                },
+            ) {
+                emit_diagnostic_error!(format!(
+                    "an error occurred while emitting event '{}': {}",
+                    event, err
+                ));
+            }
+        }
     });
     system_mgr.register_system(-10800, |context: &EngineContextWithoutSystemManager| {
         context.event_mgr().dispatcher().emit(
@@ -179,6 +644,26 @@ pub fn run(
     system_mgr.register_system(-10500, |context: &EngineContextWithoutSystemManager| {
         context.transform_mgr_mut().update_world_matrices();
     });
+    system_mgr.register_system(-2, |context: &EngineContextWithoutSystemManager| {
+        let requests = take(&mut *context.focus_requests_mut());
+        let mut ui_event_mgr = context.ui_event_mgr_mut();
+
+        for request in requests {
+            ui_event_mgr.apply_focus_request(request);
+        }
+    });
+    system_mgr.register_system(-1, |context: &EngineContextWithoutSystemManager| {
+        let entities = take(&mut *context.destroy_queue_mut());
+
+        destroy_entities(
+            &mut context.world_mut(),
+            &mut context.transform_mgr_mut(),
+            &mut context.ui_mgr_mut(),
+            &mut context.entity_event_mgr_mut(),
+            &mut context.ui_event_mgr_mut(),
+            entities,
+        );
+    });
     system_mgr.register_system(0, |context: &EngineContextWithoutSystemManager| {
         context.event_mgr().dispatcher().emit(
             context.lua_mgr().lua(),
@@ -187,10 +672,12 @@ pub fn run(
             },
         );
     });
-    system_mgr.register_system(1, |context: &EngineContextWithoutSystemManager| {
-        context.render_mgr().update_uniforms(context);
-    });
-    system_mgr.register_system(100, RendererSystem::new());
+    if render {
+        system_mgr.register_system(1, |context: &EngineContextWithoutSystemManager| {
+            context.render_mgr_mut().update_uniforms(context);
+        });
+        system_mgr.register_system(100, RendererSystem::new());
+    }
     system_mgr.register_system(200, |context: &EngineContextWithoutSystemManager| {
         context.event_mgr().dispatcher().emit(
             context.lua_mgr().lua(),
@@ -201,13 +688,19 @@ pub fn run(
     });
     system_mgr.register_system(isize::MAX, |context: &EngineContextWithoutSystemManager| {
         context.screen_mgr_mut().reset_dirty();
+        context.input_mgr_mut().end_frame();
     });
+}
 
-    {
-        emit_diagnostic_info!(format!("registering asset loaders."));
+fn register_asset_loaders(rest: &EngineContextWithoutSystemManager, render: bool) {
+    emit_diagnostic_info!(format!("registering asset loaders."));
+
+    let mut asset_mgr = rest.asset_mgr_mut();
+    asset_mgr.register_loader(loader::animation_loader());
+    asset_mgr.register_loader(loader::font_loader());
 
-        let mut asset_mgr = rest.asset_mgr_mut();
-        asset_mgr.register_loader(loader::font_loader());
+    // Below assets are uploaded to the GPU as soon as they're loaded.
+    if render {
         asset_mgr.register_loader(loader::shader_loader());
         asset_mgr.register_loader(loader::sprite_loader());
         asset_mgr.register_loader(loader::sprite_atlas_loader());
@@ -215,184 +708,164 @@ pub fn run(
         asset_mgr.register_loader(loader::sprite_nine_patch_loader());
         asset_mgr.register_loader(loader::tilemap_loader());
     }
+}
 
-    {
-        emit_diagnostic_info!(format!("abjusting scale factor."));
-
-        let scale_factor = gfx_context.window().scale_factor();
-        rest.screen_mgr_mut().update_scale_factor(
-            scale_factor,
-            &LogicalSize::new(width, height).to_physical(scale_factor),
-        );
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{create_dir_all, read_to_string, remove_dir_all, write};
+    use std::sync::Mutex;
 
-    {
-        emit_diagnostic_info!(format!("executing entry script."));
+    // The scripts reach the engine through a global context, so only one may run at a time.
+    static HEADLESS: Mutex<()> = Mutex::new(());
 
-        let path = entry_script_path.as_ref();
-        let lua_mgr = rest.lua_mgr();
-        lua_mgr.execute(path, read_to_string(path)?)?;
+    // Runs the script headlessly and returns what it has written to `result.txt`.
+    fn run_script(name: &str, script: &str, frame_count: usize) -> String {
+        try_run_script(name, script, frame_count).unwrap()
     }
 
-    emit_diagnostic_info!(format!("engine is up and running."));
-
-    {
-        let screen_mgr = rest.screen_mgr();
-        resize(
-            screen_mgr.physical_width() as u32,
-            screen_mgr.physical_height() as u32,
-        );
+    fn try_run_script(name: &str, script: &str, frame_count: usize) -> Result<String, EngineError> {
+        let _guard = HEADLESS.lock().unwrap_or_else(|err| err.into_inner());
+        let base = std::env::temp_dir().join(format!("mk-{}-{}", name, std::process::id()));
+        let result = base.join("result.txt");
+
+        create_dir_all(&base).unwrap();
+        write(
+            base.join("main.lua"),
+            script.replace("RESULT", &format!("{:?}", result.display().to_string())),
+        )
+        .unwrap();
+
+        let output = run_headless(
+            100,
+            100,
+            FileSystemAssetSource::new(&base),
+            base.join("main.lua"),
+            frame_count,
+            0.1,
+        )
+        .map(|_| read_to_string(&result).unwrap_or_default());
+        remove_dir_all(&base).ok();
+        output
     }
-    clear();
-    gfx_context.swap_buffers().unwrap();
-    gfx_context.window().set_visible(true);
-
-    let window_id = gfx_context.window().id();
 
-    event_loop.run(move |event, _, control_flow| {
-        *control_flow = ControlFlow::Poll;
-
-        system_mgr.run(&rest, isize::MIN, -1);
-
-        match event {
-            Event::MainEventsCleared => {}
-            Event::WindowEvent {
-                event: WindowEvent::KeyboardInput { input, .. },
-                window_id: id,
-            } if id == window_id => {
-                rest.input_mgr_mut().handle_event(&input);
-
-                if let Some(key) = input.virtual_keycode {
-                    match input.state {
-                        ElementState::Pressed => {
-                            rest.event_mgr()
-                                .dispatcher()
-                                .emit(rest.lua_mgr().lua(), &events::KeyDown::from_key(key));
-                        }
-                        ElementState::Released => {
-                            rest.event_mgr()
-                                .dispatcher()
-                                .emit(rest.lua_mgr().lua(), &events::KeyUp::from_key(key));
-                        }
-                    }
-                }
-
-                return;
-            }
-            Event::WindowEvent {
-                event: WindowEvent::CursorEntered { .. },
-                window_id: id,
-            } if id == window_id => {
-                rest.event_mgr()
-                    .dispatcher()
-                    .emit(rest.lua_mgr().lua(), &events::PointerEnter);
+    #[test]
+    fn broken_entry_scripts_fail_the_run() {
+        assert!(try_run_script("broken", "local is = ", 1).is_err());
+    }
 
-                return;
-            }
-            Event::WindowEvent {
-                event: WindowEvent::CursorLeft { .. },
-                window_id: id,
-            } if id == window_id => {
-                rest.event_mgr()
-                    .dispatcher()
-                    .emit(rest.lua_mgr().lua(), &events::PointerExit);
-                rest.ui_event_mgr_mut().handle_mouse_exit();
+    #[test]
+    fn entities_destroy_themselves_from_their_listeners() {
+        let output = run_script(
+            "destroy",
+            r#"
+            local entity = mk.Entity.build({ name = "self" })
+            local destroyed = 0
+            entity:listen("destroy", function() destroyed = destroyed + 1 end)
+            entity:listen("tween-finish", function()
+                entity:destroy()
+                entity:destroy()
+            end)
+            mk.Tween.play(entity, {
+                duration = 0.05,
+                from = { x = 0, y = 0 },
+                to = { x = 1, y = 1 },
+                component = "transform",
+                field = "position",
+            })
+            mk.Event.PostRender.listen(function()
+                local file = io.open(RESULT, "w")
+                file:write(tostring(destroyed), " ", tostring(entity.transform == nil))
+                file:close()
+            end)
+            "#,
+            2,
+        );
 
-                return;
-            }
-            Event::WindowEvent {
-                event: WindowEvent::CursorMoved { position, .. },
-                window_id: id,
-            } if id == window_id => {
-                let position = position.to_logical(rest.screen_mgr().scale_factor());
+        assert_eq!(output, "1 true");
+    }
 
-                rest.event_mgr().dispatcher().emit(
-                    rest.lua_mgr().lua(),
-                    &events::PointerMove {
-                        pointer_x: position.x,
-                        pointer_y: position.y,
-                    },
-                );
-                rest.ui_event_mgr_mut()
-                    .handle_mouse_move(position.x as f32, position.y as f32);
+    #[test]
+    fn destroy_listeners_listen_and_unlisten() {
+        let output = run_script(
+            "destroy-listeners",
+            r#"
+            local entity = mk.Entity.build({ name = "self" })
+            local events = {}
+            local handler
+            handler = entity:listen("destroy", function()
+                entity:unlisten(handler)
+                entity:listen("destroy", function() end)
+                table.insert(events, "destroy")
+            end)
+            entity:destroy()
+            mk.Event.PostRender.listen(function()
+                local file = io.open(RESULT, "w")
+                file:write(table.concat(events, " "))
+                file:close()
+            end)
+            "#,
+            1,
+        );
 
-                return;
-            }
-            Event::WindowEvent {
-                event: WindowEvent::MouseInput { button, state, .. },
-                window_id: id,
-            } if id == window_id => {
-                let button_name = match button {
-                    MouseButton::Left => "left",
-                    MouseButton::Right => "right",
-                    MouseButton::Middle => "middle",
-                    _ => return,
-                };
+        assert_eq!(output, "destroy");
+    }
 
-                match state {
-                    ElementState::Pressed => {
-                        rest.event_mgr().dispatcher().emit(
-                            rest.lua_mgr().lua(),
-                            &events::PointerDown {
-                                button: button_name,
-                            },
-                        );
-                        rest.ui_event_mgr_mut().handle_mouse_button_down(button);
-                    }
-                    ElementState::Released => {
-                        rest.event_mgr().dispatcher().emit(
-                            rest.lua_mgr().lua(),
-                            &events::PointerUp {
-                                button: button_name,
-                            },
-                        );
-                        rest.ui_event_mgr_mut().handle_mouse_button_up(button);
-                    }
-                }
+    #[test]
+    fn tween_finish_listeners_unlisten_themselves() {
+        let output = run_script(
+            "tween-finish",
+            r#"
+            local entity = mk.Entity.build({ name = "self" })
+            local finished = 0
+            local handler
+            handler = entity:listen("tween-finish", function()
+                finished = finished + 1
+                entity:unlisten(handler)
+            end)
+            for _ = 1, 2 do
+                mk.Tween.play(entity, {
+                    duration = 0.05,
+                    from = { x = 0, y = 0 },
+                    to = { x = 1, y = 1 },
+                    component = "transform",
+                    field = "position",
+                })
+            end
+            mk.Event.PostRender.listen(function()
+                local file = io.open(RESULT, "w")
+                file:write(tostring(finished))
+                file:close()
+            end)
+            "#,
+            2,
+        );
 
-                return;
-            }
-            Event::WindowEvent {
-                event: WindowEvent::Resized(inner_size),
-                window_id: id,
-            } if id == window_id => {
-                rest.screen_mgr_mut().update_size(&inner_size);
-                gfx_context.resize(inner_size);
-                return;
-            }
-            Event::WindowEvent {
-                event:
-                    WindowEvent::ScaleFactorChanged {
-                        scale_factor,
-                        new_inner_size,
-                    },
-                window_id: id,
-            } if id == window_id => {
-                rest.screen_mgr_mut()
-                    .update_scale_factor(scale_factor, new_inner_size);
-                gfx_context.resize(*new_inner_size);
-                return;
-            }
-            Event::WindowEvent {
-                event: WindowEvent::CloseRequested,
-                window_id: id,
-            } if id == window_id => {
-                *control_flow = ControlFlow::Exit;
-                return;
-            }
-            _ => return,
-        }
+        assert_eq!(output, "1");
+    }
 
-        {
-            let screen_mgr = rest.screen_mgr();
-            resize(
-                screen_mgr.physical_width() as u32,
-                screen_mgr.physical_height() as u32,
-            );
-        }
-        clear();
-        system_mgr.run(&rest, 0, isize::MAX);
+    #[test]
+    fn entities_change_focus_from_their_listeners() {
+        let output = run_script(
+            "focus",
+            r#"
+            local entity = mk.Entity.build({ name = "input" })
+            local events = {}
+            entity:listen("focus-in", function()
+                table.insert(events, "in")
+                entity:blur()
+            end)
+            entity:listen("focus-out", function() table.insert(events, "out") end)
+            entity:focus()
+            mk.Event.PostRender.listen(function()
+                local file = io.open(RESULT, "w")
+                file:write(table.concat(events, " "))
+                file:close()
+            end)
+            "#,
+            2,
+        );
 
-        gfx_context.swap_buffers().unwrap();
-    });
+        assert_eq!(output, "in out");
+    }
 }
@@ -1,17 +1,17 @@
+use crate::animation::TweenManager;
 use crate::api::LuaManager;
-use crate::asset::AssetManager;
+use crate::asset::{AssetManager, AssetSource};
 use crate::event::{EntityEventManager, EventManager};
 use crate::glyph::GlyphManager;
-use crate::input::InputManager;
+use crate::input::{Clipboard, InputManager, MemoryClipboard, VirtualGamepads};
 use crate::render::{RenderManager, ScreenManager};
 use crate::system::SystemManager;
 use crate::time::TimeManager;
 use crate::transform::TransformManager;
-use crate::ui::{UIEventManager, UIManager};
+use crate::ui::{FocusRequest, UIEventManager, UIManager};
 use crate::EngineError;
-use legion::World;
+use legion::{Entity, World};
 use std::cell::{Ref, RefCell, RefMut};
-use std::path::PathBuf;
 use std::sync::Arc;
 
 pub struct EngineContextWithoutSystemManager {
@@ -28,16 +28,21 @@ pub struct EngineContextWithoutSystemManager {
     render_mgr: RefCell<RenderManager>,
     ui_mgr: RefCell<UIManager>,
     ui_event_mgr: RefCell<UIEventManager>,
+    tween_mgr: RefCell<TweenManager>,
+    clipboard: RefCell<Box<dyn Clipboard>>,
+    virtual_gamepads: RefCell<Option<VirtualGamepads>>,
+    destroy_queue: RefCell<Vec<Entity>>,
+    focus_requests: RefCell<Vec<FocusRequest>>,
 }
 
 impl EngineContextWithoutSystemManager {
-    pub fn new(screen_width: u32, screen_height: u32, asset_mgr_base: PathBuf) -> Self {
+    pub fn new(screen_width: u32, screen_height: u32, asset_source: Box<dyn AssetSource>) -> Self {
         Self {
             world: World::default().into(),
             time_mgr: TimeManager::new().into(),
             input_mgr: InputManager::new().into(),
             screen_mgr: ScreenManager::new(screen_width, screen_height).into(),
-            asset_mgr: AssetManager::new(asset_mgr_base).into(),
+            asset_mgr: AssetManager::new(asset_source).into(),
             transform_mgr: TransformManager::new().into(),
             lua_mgr: LuaManager::new(),
             event_mgr: EventManager::new(),
@@ -46,6 +51,11 @@ impl EngineContextWithoutSystemManager {
             render_mgr: RenderManager::new().into(),
             ui_mgr: UIManager::new().into(),
             ui_event_mgr: UIEventManager::new().into(),
+            tween_mgr: TweenManager::new().into(),
+            clipboard: RefCell::new(Box::new(MemoryClipboard::new())),
+            virtual_gamepads: None.into(),
+            destroy_queue: Vec::new().into(),
+            focus_requests: Vec::new().into(),
         }
     }
 
@@ -144,6 +154,41 @@ impl EngineContextWithoutSystemManager {
     pub fn ui_event_mgr_mut(&self) -> RefMut<UIEventManager> {
         self.ui_event_mgr.borrow_mut()
     }
+
+    pub fn tween_mgr(&self) -> Ref<TweenManager> {
+        self.tween_mgr.borrow()
+    }
+
+    pub fn tween_mgr_mut(&self) -> RefMut<TweenManager> {
+        self.tween_mgr.borrow_mut()
+    }
+
+    pub fn clipboard_mut(&self) -> RefMut<Box<dyn Clipboard>> {
+        self.clipboard.borrow_mut()
+    }
+
+    pub fn set_clipboard(&self, clipboard: Box<dyn Clipboard>) {
+        *self.clipboard.borrow_mut() = clipboard;
+    }
+
+    // The gamepads to inject events with, if the engine runs without real gamepads (headless mode).
+    pub fn virtual_gamepads(&self) -> Option<VirtualGamepads> {
+        self.virtual_gamepads.borrow().clone()
+    }
+
+    pub fn set_virtual_gamepads(&self, gamepads: Option<VirtualGamepads>) {
+        *self.virtual_gamepads.borrow_mut() = gamepads;
+    }
+
+    // Entities destroyed by scripts, removed by the destroy system once no event is being dispatched.
+    pub fn destroy_queue_mut(&self) -> RefMut<Vec<Entity>> {
+        self.destroy_queue.borrow_mut()
+    }
+
+    // Focus changes requested by scripts, applied by the focus system for the same reason.
+    pub fn focus_requests_mut(&self) -> RefMut<Vec<FocusRequest>> {
+        self.focus_requests.borrow_mut()
+    }
 }
 
 impl Drop for EngineContextWithoutSystemManager {
@@ -161,14 +206,14 @@ impl EngineContext {
     pub fn new(
         screen_width: u32,
         screen_height: u32,
-        asset_mgr_base: PathBuf,
+        asset_source: Box<dyn AssetSource>,
     ) -> Result<Self, EngineError> {
         Ok(Self {
             system_mgr: SystemManager::default(),
             context: Arc::new(EngineContextWithoutSystemManager::new(
                 screen_width,
                 screen_height,
-                asset_mgr_base,
+                asset_source,
             )),
         })
     }
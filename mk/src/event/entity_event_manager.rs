@@ -1,3 +1,4 @@
+use crate::api::use_context;
 use crate::event::events::PerEntity;
 use crate::util::BoxId;
 use legion::Entity;
@@ -45,18 +46,33 @@ impl EntityEventManager {
         self.listeners_per_entity.remove(&entity);
     }
 
-    pub fn emit<'lua>(&self, lua: &'lua Lua, event: &'lua PerEntity<'lua>) -> LuaResult<()> {
-        if let Some(listeners) = self.listeners_per_entity.get(&event.entity) {
-            for (event_ty, listener) in listeners {
-                if event_ty != &event.event {
-                    continue;
-                }
+    // The listeners are taken out before being called, so that they can listen and unlisten freely.
+    pub fn emit<'lua>(lua: &'lua Lua, event: &PerEntity<'lua>) -> LuaResult<()> {
+        let listeners =
+            use_context()
+                .entity_event_mgr()
+                .listeners(lua, event.entity, &event.event)?;
 
-                lua.registry_value::<LuaFunction>(&listener)?
-                    .call::<_, ()>(event.param.clone())?;
-            }
+        for listener in listeners {
+            listener.call::<_, ()>(event.param.clone())?;
         }
 
         Ok(())
     }
+
+    fn listeners<'lua>(
+        &self,
+        lua: &'lua Lua,
+        entity: Entity,
+        event: &str,
+    ) -> LuaResult<Vec<LuaFunction<'lua>>> {
+        match self.listeners_per_entity.get(&entity) {
+            Some(listeners) => listeners
+                .iter()
+                .filter(|(event_ty, _)| event_ty == event)
+                .map(|(_, listener)| lua.registry_value(listener))
+                .collect(),
+            None => Ok(Vec::new()),
+        }
+    }
 }
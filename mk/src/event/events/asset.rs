@@ -0,0 +1,69 @@
+use crate::asset::{asset_to_lua, AssetLoadHandle, AssetReload};
+use mlua::prelude::*;
+use mlua::UserData;
+use std::any::type_name;
+
+#[derive(Debug, Clone)]
+pub struct AssetLoaded {
+    pub handle: AssetLoadHandle,
+}
+
+impl_event_type_lua_api!(AssetLoaded);
+
+impl UserData for AssetLoaded {
+    fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_meta_method(LuaMetaMethod::Index, |lua, this, value: String| {
+            Ok(match value.as_str() {
+                "handle" => this.handle.clone().to_lua(lua)?,
+                _ => {
+                    return Err(format!(
+                        "property '{}' is not exists on the '{}'",
+                        value,
+                        type_name::<Self>()
+                    )
+                    .to_lua_err())
+                }
+            })
+        });
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AssetReloaded {
+    pub reload: AssetReload,
+}
+
+impl_event_type_lua_api!(AssetReloaded);
+
+impl UserData for AssetReloaded {
+    fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_meta_method(LuaMetaMethod::Index, |lua, this, value: String| {
+            Ok(match value.as_str() {
+                "path" => this.reload.path.to_string_lossy().to_lua(lua)?,
+                "type" => this
+                    .reload
+                    .type_name
+                    .rsplit("::")
+                    .next()
+                    .unwrap_or(this.reload.type_name)
+                    .to_lua(lua)?,
+                "asset" => match &this.reload.result {
+                    Ok(asset) => asset_to_lua(lua, asset)?,
+                    Err(..) => LuaValue::Nil,
+                },
+                "error" => match &this.reload.result {
+                    Ok(..) => LuaValue::Nil,
+                    Err(err) => err.as_str().to_lua(lua)?,
+                },
+                _ => {
+                    return Err(format!(
+                        "property '{}' is not exists on the '{}'",
+                        value,
+                        type_name::<Self>()
+                    )
+                    .to_lua_err())
+                }
+            })
+        });
+    }
+}
@@ -126,3 +126,28 @@ impl UserData for PostRender {
         });
     }
 }
+
+#[derive(Debug, Clone, Copy)]
+pub struct EntityDestroy {
+    pub entity: legion::Entity,
+}
+
+impl_event_type_lua_api!(EntityDestroy);
+
+impl UserData for EntityDestroy {
+    fn add_methods<'lua, M: LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_meta_method(LuaMetaMethod::Index, |lua, this, value: String| {
+            Ok(match value.as_str() {
+                "entity" => crate::api::Entity::new(this.entity).to_lua(lua)?,
+                _ => {
+                    return Err(format!(
+                        "property '{}' is not exists on the '{}'",
+                        value,
+                        type_name::<Self>()
+                    )
+                    .to_lua_err())
+                }
+            })
+        });
+    }
+}
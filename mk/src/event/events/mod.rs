@@ -13,21 +13,31 @@ macro_rules! impl_event_type_lua_api {
                 table.set(
                     "listen",
                     lua.create_function(|lua, handler: mlua::Function| {
-                        Ok(crate::api::use_context()
-                            .event_mgr()
-                            .dispatcher()
-                            .add_listener::<Self>(crate::event::TypedEventListener::LuaFunction(
-                                crate::util::BoxId::new(lua.create_registry_value(handler)?),
-                            )))
+                        let context = crate::api::use_context();
+                        let hash = context.event_mgr().dispatcher().add_listener::<Self>(
+                            crate::event::TypedEventListener::LuaFunction(crate::util::BoxId::new(
+                                lua.create_registry_value(handler)?,
+                            )),
+                        );
+                        context.lua_mgr().track_listener(lua, hash, move || {
+                            crate::api::use_context()
+                                .event_mgr()
+                                .dispatcher()
+                                .remove_listener::<Self>(hash)
+                        });
+                        Ok(hash)
                     })?,
                 )?;
                 table.set(
                     "unlisten",
                     lua.create_function(|_lua, handler: usize| {
-                        Ok(crate::api::use_context()
+                        let context = crate::api::use_context();
+                        context
                             .event_mgr()
                             .dispatcher()
-                            .remove_listener::<Self>(handler))
+                            .remove_listener::<Self>(handler);
+                        context.lua_mgr().untrack_listener(handler);
+                        Ok(())
                     })?,
                 )?;
                 Ok(())
@@ -36,11 +46,13 @@ macro_rules! impl_event_type_lua_api {
     };
 }
 
+mod asset;
 mod diagnostic;
 mod input;
 mod lifecycles;
 mod per_entity;
 
+pub use asset::*;
 pub use diagnostic::*;
 pub use input::*;
 pub use lifecycles::*;
@@ -0,0 +1,74 @@
+// Copy and paste of plain text.
+pub trait Clipboard {
+    fn get_text(&mut self) -> Option<String>;
+    fn set_text(&mut self, text: String);
+}
+
+// A clipboard private to the engine, used when the system one is not available.
+#[derive(Default, Debug, Clone)]
+pub struct MemoryClipboard {
+    text: Option<String>,
+}
+
+impl MemoryClipboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.text = Some(text);
+    }
+}
+
+#[cfg(feature = "clipboard")]
+pub struct SystemClipboard {
+    clipboard: arboard::Clipboard,
+}
+
+#[cfg(feature = "clipboard")]
+impl SystemClipboard {
+    pub fn new() -> Result<Self, arboard::Error> {
+        Ok(Self {
+            clipboard: arboard::Clipboard::new()?,
+        })
+    }
+}
+
+#[cfg(feature = "clipboard")]
+impl Clipboard for SystemClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        // Non-text contents are treated as empty.
+        self.clipboard.get_text().ok()
+    }
+
+    fn set_text(&mut self, text: String) {
+        if let Err(err) = self.clipboard.set_text(text) {
+            crate::emit_diagnostic_warn!(format!(
+                "unable to copy the text to the clipboard due to: {}",
+                err
+            ));
+        }
+    }
+}
+
+// Uses the system clipboard if the `clipboard` feature is enabled and it's available.
+pub fn default_clipboard() -> Box<dyn Clipboard> {
+    #[cfg(feature = "clipboard")]
+    match SystemClipboard::new() {
+        Ok(clipboard) => return Box::new(clipboard),
+        Err(err) => {
+            crate::emit_diagnostic_warn!(format!(
+                "unable to access the system clipboard due to: {}; falling back to a private one.",
+                err
+            ));
+        }
+    }
+
+    Box::new(MemoryClipboard::new())
+}
@@ -0,0 +1,160 @@
+use crate::structure::Vec2;
+use mlua::prelude::*;
+use serde::{de::Error, Deserialize, Deserializer};
+
+macro_rules! gamepad_names {
+    ($type:ident { $($variant:ident => $name:literal,)* }) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $type {
+            $($variant,)*
+        }
+
+        impl $type {
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $($name => Some(Self::$variant),)*
+                    _ => None,
+                }
+            }
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)*
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let name = String::deserialize(deserializer)?;
+                Self::from_name(&name).ok_or_else(|| {
+                    D::Error::custom(format!(
+                        "{:?} is invalid value for the type {}",
+                        name,
+                        stringify!($type)
+                    ))
+                })
+            }
+        }
+
+        impl<'lua> FromLua<'lua> for $type {
+            fn from_lua(value: LuaValue<'lua>, lua: &'lua Lua) -> LuaResult<Self> {
+                let str = String::from_lua(value, lua)?;
+                Self::from_name(&str).ok_or_else(|| {
+                    format!("{:?} is invalid value for the type {}", str, stringify!($type))
+                        .to_lua_err()
+                })
+            }
+        }
+
+        impl<'lua> ToLua<'lua> for $type {
+            fn to_lua(self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+                self.name().to_lua(lua)
+            }
+        }
+    };
+}
+
+// Buttons are named after their position, e.g. `south` is A on Xbox and Cross on PlayStation pads.
+gamepad_names!(GamepadButton {
+    South => "south",
+    East => "east",
+    North => "north",
+    West => "west",
+    LeftShoulder => "left_shoulder",
+    RightShoulder => "right_shoulder",
+    LeftTrigger => "left_trigger",
+    RightTrigger => "right_trigger",
+    Select => "select",
+    Start => "start",
+    Mode => "mode",
+    LeftStick => "left_stick",
+    RightStick => "right_stick",
+    DPadUp => "dpad_up",
+    DPadDown => "dpad_down",
+    DPadLeft => "dpad_left",
+    DPadRight => "dpad_right",
+});
+
+// Sticks range from -1 to 1 with up being positive; triggers range from 0 to 1.
+gamepad_names!(GamepadAxis {
+    LeftStickX => "left_stick_x",
+    LeftStickY => "left_stick_y",
+    RightStickX => "right_stick_x",
+    RightStickY => "right_stick_y",
+    LeftTrigger => "left_trigger",
+    RightTrigger => "right_trigger",
+});
+
+gamepad_names!(GamepadStick {
+    Left => "left",
+    Right => "right",
+});
+
+impl GamepadStick {
+    pub fn axes(self) -> (GamepadAxis, GamepadAxis) {
+        match self {
+            Self::Left => (GamepadAxis::LeftStickX, GamepadAxis::LeftStickY),
+            Self::Right => (GamepadAxis::RightStickX, GamepadAxis::RightStickY),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GamepadId(pub usize);
+
+impl<'lua> FromLua<'lua> for GamepadId {
+    fn from_lua(value: LuaValue<'lua>, lua: &'lua Lua) -> LuaResult<Self> {
+        Ok(Self(usize::from_lua(value, lua)?))
+    }
+}
+
+impl<'lua> ToLua<'lua> for GamepadId {
+    fn to_lua(self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        self.0.to_lua(lua)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GamepadEvent {
+    Connected {
+        id: GamepadId,
+        name: String,
+    },
+    Disconnected {
+        id: GamepadId,
+    },
+    Button {
+        id: GamepadId,
+        button: GamepadButton,
+        is_pressed: bool,
+    },
+    Axis {
+        id: GamepadId,
+        axis: GamepadAxis,
+        value: f32,
+    },
+}
+
+// Rescales the value so that the output starts from zero right at the edge of the dead zone.
+pub fn apply_axial_dead_zone(value: f32, dead_zone: f32) -> f32 {
+    if value.abs() <= dead_zone {
+        0f32
+    } else {
+        value.signum() * ((value.abs() - dead_zone) / (1f32 - dead_zone)).min(1f32)
+    }
+}
+
+// Same as above, but based on the length so that the stick keeps its direction near the axes.
+pub fn apply_radial_dead_zone(value: Vec2, dead_zone: f32) -> Vec2 {
+    let len = value.len();
+
+    if len <= dead_zone {
+        Vec2::default()
+    } else {
+        value.norm() * ((len - dead_zone) / (1f32 - dead_zone)).min(1f32)
+    }
+}
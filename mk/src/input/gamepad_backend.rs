@@ -0,0 +1,221 @@
+use crate::input::{GamepadAxis, GamepadButton, GamepadEvent, GamepadId};
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+// A source of gamepad events, polled once per frame.
+pub trait GamepadBackend {
+    fn poll(&mut self, events: &mut Vec<GamepadEvent>);
+}
+
+// A backend without any hardware; events are injected through `VirtualGamepads`.
+pub struct VirtualGamepadBackend {
+    gamepads: VirtualGamepads,
+    receiver: Receiver<GamepadEvent>,
+}
+
+impl VirtualGamepadBackend {
+    pub fn new() -> Self {
+        let (sender, receiver) = unbounded();
+
+        Self {
+            gamepads: VirtualGamepads {
+                sender,
+                next_id: Arc::new(AtomicUsize::new(0)),
+            },
+            receiver,
+        }
+    }
+
+    pub fn gamepads(&self) -> VirtualGamepads {
+        self.gamepads.clone()
+    }
+}
+
+impl Default for VirtualGamepadBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GamepadBackend for VirtualGamepadBackend {
+    fn poll(&mut self, events: &mut Vec<GamepadEvent>) {
+        events.extend(self.receiver.try_iter());
+    }
+}
+
+// Injects events into a `VirtualGamepadBackend`; they are delivered on its next poll.
+#[derive(Debug, Clone)]
+pub struct VirtualGamepads {
+    sender: Sender<GamepadEvent>,
+    next_id: Arc<AtomicUsize>,
+}
+
+impl VirtualGamepads {
+    pub fn connect(&self, name: impl Into<String>) -> GamepadId {
+        let id = GamepadId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        self.send(GamepadEvent::Connected {
+            id,
+            name: name.into(),
+        });
+        id
+    }
+
+    pub fn disconnect(&self, id: GamepadId) {
+        self.send(GamepadEvent::Disconnected { id });
+    }
+
+    pub fn press(&self, id: GamepadId, button: GamepadButton) {
+        self.send(GamepadEvent::Button {
+            id,
+            button,
+            is_pressed: true,
+        });
+    }
+
+    pub fn release(&self, id: GamepadId, button: GamepadButton) {
+        self.send(GamepadEvent::Button {
+            id,
+            button,
+            is_pressed: false,
+        });
+    }
+
+    pub fn set_axis(&self, id: GamepadId, axis: GamepadAxis, value: f32) {
+        self.send(GamepadEvent::Axis { id, axis, value });
+    }
+
+    fn send(&self, event: GamepadEvent) {
+        // The backend may have been dropped already; there's no one to deliver to then.
+        self.sender.send(event).ok();
+    }
+}
+
+#[cfg(feature = "gamepad")]
+pub struct GilrsGamepadBackend {
+    gilrs: gilrs::Gilrs,
+    // gilrs doesn't report the gamepads plugged in before it starts, so they're connected on the first poll.
+    pending: Vec<GamepadEvent>,
+}
+
+#[cfg(feature = "gamepad")]
+impl GilrsGamepadBackend {
+    pub fn new() -> Result<Self, Box<gilrs::Error>> {
+        let gilrs = gilrs::Gilrs::new().map_err(Box::new)?;
+        let pending = gilrs
+            .gamepads()
+            .map(|(id, gamepad)| GamepadEvent::Connected {
+                id: GamepadId(id.into()),
+                name: gamepad.name().to_owned(),
+            })
+            .collect();
+
+        Ok(Self { gilrs, pending })
+    }
+}
+
+#[cfg(feature = "gamepad")]
+impl GamepadBackend for GilrsGamepadBackend {
+    fn poll(&mut self, events: &mut Vec<GamepadEvent>) {
+        use gilrs::{Axis, Button, EventType};
+
+        fn map_button(button: Button) -> Option<GamepadButton> {
+            Some(match button {
+                Button::South => GamepadButton::South,
+                Button::East => GamepadButton::East,
+                Button::North => GamepadButton::North,
+                Button::West => GamepadButton::West,
+                Button::LeftTrigger => GamepadButton::LeftShoulder,
+                Button::RightTrigger => GamepadButton::RightShoulder,
+                Button::LeftTrigger2 => GamepadButton::LeftTrigger,
+                Button::RightTrigger2 => GamepadButton::RightTrigger,
+                Button::Select => GamepadButton::Select,
+                Button::Start => GamepadButton::Start,
+                Button::Mode => GamepadButton::Mode,
+                Button::LeftThumb => GamepadButton::LeftStick,
+                Button::RightThumb => GamepadButton::RightStick,
+                Button::DPadUp => GamepadButton::DPadUp,
+                Button::DPadDown => GamepadButton::DPadDown,
+                Button::DPadLeft => GamepadButton::DPadLeft,
+                Button::DPadRight => GamepadButton::DPadRight,
+                _ => return None,
+            })
+        }
+
+        fn map_axis(axis: Axis) -> Option<GamepadAxis> {
+            Some(match axis {
+                Axis::LeftStickX => GamepadAxis::LeftStickX,
+                Axis::LeftStickY => GamepadAxis::LeftStickY,
+                Axis::RightStickX => GamepadAxis::RightStickX,
+                Axis::RightStickY => GamepadAxis::RightStickY,
+                _ => return None,
+            })
+        }
+
+        events.append(&mut self.pending);
+
+        while let Some(gilrs::Event { id, event, .. }) = self.gilrs.next_event() {
+            let gamepad = GamepadId(id.into());
+
+            events.push(match event {
+                EventType::Connected => GamepadEvent::Connected {
+                    id: gamepad,
+                    name: self.gilrs.gamepad(id).name().to_owned(),
+                },
+                EventType::Disconnected => GamepadEvent::Disconnected { id: gamepad },
+                EventType::ButtonPressed(button, _) => match map_button(button) {
+                    Some(button) => GamepadEvent::Button {
+                        id: gamepad,
+                        button,
+                        is_pressed: true,
+                    },
+                    None => continue,
+                },
+                EventType::ButtonReleased(button, _) => match map_button(button) {
+                    Some(button) => GamepadEvent::Button {
+                        id: gamepad,
+                        button,
+                        is_pressed: false,
+                    },
+                    None => continue,
+                },
+                // Analog triggers are reported as buttons with values.
+                EventType::ButtonChanged(Button::LeftTrigger2, value, _) => GamepadEvent::Axis {
+                    id: gamepad,
+                    axis: GamepadAxis::LeftTrigger,
+                    value,
+                },
+                EventType::ButtonChanged(Button::RightTrigger2, value, _) => GamepadEvent::Axis {
+                    id: gamepad,
+                    axis: GamepadAxis::RightTrigger,
+                    value,
+                },
+                EventType::AxisChanged(axis, value, _) => match map_axis(axis) {
+                    Some(axis) => GamepadEvent::Axis {
+                        id: gamepad,
+                        axis,
+                        value,
+                    },
+                    None => continue,
+                },
+                _ => continue,
+            });
+        }
+    }
+}
+
+// Uses the hardware backend if the `gamepad` feature is enabled and it's available.
+pub fn default_gamepad_backend() -> Box<dyn GamepadBackend> {
+    #[cfg(feature = "gamepad")]
+    match GilrsGamepadBackend::new() {
+        Ok(backend) => return Box::new(backend),
+        Err(err) => {
+            crate::emit_diagnostic_warn!(format!(
+                "unable to initialize gamepads due to: {}; gamepads will not be available.",
+                err
+            ));
+        }
+    }
+
+    Box::new(VirtualGamepadBackend::new())
+}
@@ -0,0 +1,74 @@
+use crate::input::{GamepadAxis, GamepadStick, InputSource};
+use mlua::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum AxisBinding {
+    // Four directions composed into a vector, e.g. WASD; diagonals are normalized.
+    Composite {
+        left: Vec<InputSource>,
+        right: Vec<InputSource>,
+        down: Vec<InputSource>,
+        up: Vec<InputSource>,
+        // Whichever of the keys and the stick is pushed further wins.
+        #[serde(default)]
+        stick: Option<GamepadStick>,
+    },
+    Buttons {
+        negative: Vec<InputSource>,
+        positive: Vec<InputSource>,
+        #[serde(default)]
+        axis: Option<GamepadAxis>,
+    },
+    Stick {
+        stick: GamepadStick,
+    },
+    Analog {
+        axis: GamepadAxis,
+    },
+}
+
+impl<'lua> FromLua<'lua> for AxisBinding {
+    fn from_lua(value: LuaValue<'lua>, lua: &'lua Lua) -> LuaResult<Self> {
+        let table = LuaTable::from_lua(value, lua)?;
+
+        if table.contains_key("negative")? || table.contains_key("positive")? {
+            Ok(Self::Buttons {
+                negative: table.get("negative")?,
+                positive: table.get("positive")?,
+                axis: table.get("axis")?,
+            })
+        } else if table.contains_key("left")?
+            || table.contains_key("right")?
+            || table.contains_key("down")?
+            || table.contains_key("up")?
+        {
+            Ok(Self::Composite {
+                left: table.get("left")?,
+                right: table.get("right")?,
+                down: table.get("down")?,
+                up: table.get("up")?,
+                stick: table.get("stick")?,
+            })
+        } else if table.contains_key("stick")? {
+            Ok(Self::Stick {
+                stick: table.get("stick")?,
+            })
+        } else {
+            Ok(Self::Analog {
+                axis: table.get("axis")?,
+            })
+        }
+    }
+}
+
+// The bindings loaded from a config file, merged into the ones already defined.
+#[derive(Deserialize, Default, Debug, Clone, PartialEq)]
+pub struct InputBindings {
+    #[serde(default)]
+    pub actions: HashMap<String, Vec<InputSource>>,
+    #[serde(default)]
+    pub axes: HashMap<String, AxisBinding>,
+}
@@ -1,39 +1,464 @@
-use crate::input::InputType;
-use glutin::event::KeyboardInput;
-use parking_lot::Mutex;
-use std::collections::HashMap;
-use std::sync::Arc;
+use crate::input::{
+    apply_axial_dead_zone, apply_radial_dead_zone, AxisBinding, GamepadAxis, GamepadButton,
+    GamepadEvent, GamepadId, GamepadStick, InputBindings, InputSource,
+};
+use crate::structure::Vec2;
+use glutin::event::{ElementState, KeyboardInput, ModifiersState, MouseButton};
+use mlua::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-pub type Input = Arc<Mutex<dyn InputType>>;
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AxisValue {
+    Scalar(f32),
+    Vector(Vec2),
+}
+
+impl<'lua> ToLua<'lua> for AxisValue {
+    fn to_lua(self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        match self {
+            Self::Scalar(value) => value.to_lua(lua),
+            Self::Vector(value) => value.to_lua(lua),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GamepadState {
+    pub name: String,
+    buttons: HashSet<GamepadButton>,
+    // Raw values; the dead zone is applied when they're read.
+    axes: HashMap<GamepadAxis, f32>,
+}
 
+#[derive(Debug)]
 pub struct InputManager {
-    inputs: HashMap<String, Input>,
+    down: HashSet<InputSource>,
+    // Sources pressed or released since the end of the last frame.
+    pressed: HashSet<InputSource>,
+    released: HashSet<InputSource>,
+    actions: HashMap<String, Vec<InputSource>>,
+    axes: HashMap<String, AxisBinding>,
+    gamepads: BTreeMap<GamepadId, GamepadState>,
+    dead_zone: f32,
+    modifiers: ModifiersState,
+}
+
+impl Default for InputManager {
+    fn default() -> Self {
+        Self {
+            down: HashSet::new(),
+            pressed: HashSet::new(),
+            released: HashSet::new(),
+            actions: HashMap::new(),
+            axes: HashMap::new(),
+            gamepads: BTreeMap::new(),
+            dead_zone: 0.2f32,
+            modifiers: ModifiersState::empty(),
+        }
+    }
 }
 
 impl InputManager {
     pub fn new() -> InputManager {
-        InputManager {
-            inputs: HashMap::new(),
-        }
+        Self::default()
     }
 
-    pub fn input<S: AsRef<String>>(&self, name: S) -> Option<Input> {
-        self.inputs.get(name.as_ref()).cloned()
+    pub fn dead_zone(&self) -> f32 {
+        self.dead_zone
     }
 
-    pub fn register<T: InputType + 'static>(&mut self, name: String, input: T) -> Input {
-        let input = Arc::new(Mutex::new(input));
-        self.inputs.insert(name, input.clone());
-        input
+    pub fn set_dead_zone(&mut self, dead_zone: f32) {
+        self.dead_zone = dead_zone;
     }
 
-    pub fn unregister<S: AsRef<String>>(&mut self, name: S) {
-        self.inputs.remove(name.as_ref());
+    pub fn define_action(&mut self, name: String, sources: Vec<InputSource>) {
+        self.actions.insert(name, sources);
+    }
+
+    pub fn define_axis(&mut self, name: String, binding: AxisBinding) {
+        self.axes.insert(name, binding);
+    }
+
+    pub fn remove_action(&mut self, name: &str) {
+        self.actions.remove(name);
+    }
+
+    pub fn remove_axis(&mut self, name: &str) {
+        self.axes.remove(name);
+    }
+
+    pub fn apply_bindings(&mut self, bindings: InputBindings) {
+        self.actions.extend(bindings.actions);
+        self.axes.extend(bindings.axes);
+    }
+
+    pub fn modifiers(&self) -> ModifiersState {
+        self.modifiers
+    }
+
+    pub fn handle_modifiers(&mut self, modifiers: ModifiersState) {
+        self.modifiers = modifiers;
     }
 
     pub fn handle_event(&mut self, input: &KeyboardInput) {
-        for input_type in self.inputs.values() {
-            input_type.lock().handle_event(input);
+        if let Some(keycode) = input.virtual_keycode {
+            self.handle_source(InputSource::Key(keycode), input.state);
+        }
+    }
+
+    pub fn handle_mouse_button(&mut self, button: MouseButton, state: ElementState) {
+        self.handle_source(InputSource::Mouse(button.into()), state);
+    }
+
+    pub fn handle_gamepad_event(&mut self, event: &GamepadEvent) {
+        match event {
+            GamepadEvent::Connected { id, name } => {
+                self.gamepads.insert(
+                    *id,
+                    GamepadState {
+                        name: name.clone(),
+                        buttons: HashSet::new(),
+                        axes: HashMap::new(),
+                    },
+                );
+            }
+            GamepadEvent::Disconnected { id } => {
+                if let Some(gamepad) = self.gamepads.remove(id) {
+                    for button in gamepad.buttons {
+                        self.update_gamepad_button(button);
+                    }
+                }
+            }
+            GamepadEvent::Button {
+                id,
+                button,
+                is_pressed,
+            } => {
+                let gamepad = if let Some(gamepad) = self.gamepads.get_mut(id) {
+                    gamepad
+                } else {
+                    return;
+                };
+
+                if *is_pressed {
+                    gamepad.buttons.insert(*button);
+                } else {
+                    gamepad.buttons.remove(button);
+                }
+
+                self.update_gamepad_button(*button);
+            }
+            GamepadEvent::Axis { id, axis, value } => {
+                if let Some(gamepad) = self.gamepads.get_mut(id) {
+                    gamepad.axes.insert(*axis, value.clamp(-1f32, 1f32));
+                }
+            }
+        }
+    }
+
+    fn update_gamepad_button(&mut self, button: GamepadButton) {
+        let is_down = self
+            .gamepads
+            .values()
+            .any(|gamepad| gamepad.buttons.contains(&button));
+
+        self.handle_source(
+            InputSource::Gamepad(button),
+            if is_down {
+                ElementState::Pressed
+            } else {
+                ElementState::Released
+            },
+        );
+    }
+
+    pub fn handle_source(&mut self, source: InputSource, state: ElementState) {
+        match state {
+            ElementState::Pressed => {
+                // Key repeats are not presses.
+                if self.down.insert(source) {
+                    self.pressed.insert(source);
+                }
+            }
+            ElementState::Released => {
+                if self.down.remove(&source) {
+                    self.released.insert(source);
+                }
+            }
+        }
+    }
+
+    // Releases everything, e.g. when the window loses focus and the releases would be missed.
+    pub fn reset(&mut self) {
+        self.released.extend(self.down.drain());
+        self.modifiers = ModifiersState::empty();
+    }
+
+    pub fn end_frame(&mut self) {
+        self.pressed.clear();
+        self.released.clear();
+    }
+
+    pub fn is_source_down(&self, source: InputSource) -> bool {
+        self.down.contains(&source)
+    }
+
+    // Returns `None` if there's no such action.
+    pub fn is_down(&self, action: &str) -> Option<bool> {
+        self.actions
+            .get(action)
+            .map(|sources| sources.iter().any(|source| self.down.contains(source)))
+    }
+
+    pub fn just_pressed(&self, action: &str) -> Option<bool> {
+        self.actions
+            .get(action)
+            .map(|sources| sources.iter().any(|source| self.pressed.contains(source)))
+    }
+
+    // Holds only if no other source of the action is still down.
+    pub fn just_released(&self, action: &str) -> Option<bool> {
+        self.actions.get(action).map(|sources| {
+            sources.iter().any(|source| self.released.contains(source))
+                && !sources.iter().any(|source| self.down.contains(source))
+        })
+    }
+
+    pub fn axis_value(&self, axis: &str) -> Option<AxisValue> {
+        self.axes.get(axis).map(|binding| match binding {
+            AxisBinding::Buttons {
+                negative,
+                positive,
+                axis,
+            } => {
+                let value = self.direction(negative, positive);
+                let analog = axis.map_or(0f32, |axis| self.analog_axis(axis));
+
+                AxisValue::Scalar(if value.abs() < analog.abs() {
+                    analog
+                } else {
+                    value
+                })
+            }
+            AxisBinding::Composite {
+                left,
+                right,
+                down,
+                up,
+                stick,
+            } => {
+                let value = Vec2::new(self.direction(left, right), self.direction(down, up));
+                let value = if 1f32 < value.len_square() {
+                    value.norm()
+                } else {
+                    value
+                };
+                let analog = stick.map_or(Vec2::default(), |stick| self.analog_stick(stick));
+
+                AxisValue::Vector(if value.len_square() < analog.len_square() {
+                    analog
+                } else {
+                    value
+                })
+            }
+            AxisBinding::Stick { stick } => AxisValue::Vector(self.analog_stick(*stick)),
+            AxisBinding::Analog { axis } => AxisValue::Scalar(self.analog_axis(*axis)),
+        })
+    }
+
+    pub fn gamepads(&self) -> impl Iterator<Item = (GamepadId, &GamepadState)> {
+        self.gamepads.iter().map(|(&id, gamepad)| (id, gamepad))
+    }
+
+    pub fn gamepad(&self, id: GamepadId) -> Option<&GamepadState> {
+        self.gamepads.get(&id)
+    }
+
+    pub fn is_gamepad_button_down(&self, id: GamepadId, button: GamepadButton) -> bool {
+        self.gamepads
+            .get(&id)
+            .is_some_and(|gamepad| gamepad.buttons.contains(&button))
+    }
+
+    pub fn gamepad_axis(&self, id: GamepadId, axis: GamepadAxis) -> f32 {
+        self.gamepads.get(&id).map_or(0f32, |gamepad| {
+            apply_axial_dead_zone(gamepad.axis(axis), self.dead_zone)
+        })
+    }
+
+    pub fn gamepad_stick(&self, id: GamepadId, stick: GamepadStick) -> Vec2 {
+        self.gamepads.get(&id).map_or(Vec2::default(), |gamepad| {
+            apply_radial_dead_zone(gamepad.stick(stick), self.dead_zone)
+        })
+    }
+
+    // The one pushed furthest among the connected gamepads.
+    fn analog_axis(&self, axis: GamepadAxis) -> f32 {
+        self.gamepads
+            .keys()
+            .map(|&id| self.gamepad_axis(id, axis))
+            .fold(
+                0f32,
+                |lhs, rhs| if lhs.abs() < rhs.abs() { rhs } else { lhs },
+            )
+    }
+
+    fn analog_stick(&self, stick: GamepadStick) -> Vec2 {
+        self.gamepads
+            .keys()
+            .map(|&id| self.gamepad_stick(id, stick))
+            .fold(Vec2::default(), |lhs, rhs| {
+                if lhs.len_square() < rhs.len_square() {
+                    rhs
+                } else {
+                    lhs
+                }
+            })
+    }
+
+    fn direction(&self, negative: &[InputSource], positive: &[InputSource]) -> f32 {
+        let is_down =
+            |sources: &[InputSource]| sources.iter().any(|source| self.down.contains(source));
+        let mut value = 0f32;
+
+        if is_down(negative) {
+            value -= 1f32;
+        }
+
+        if is_down(positive) {
+            value += 1f32;
+        }
+
+        value
+    }
+}
+
+impl GamepadState {
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes.get(&axis).copied().unwrap_or(0f32)
+    }
+
+    pub fn stick(&self, stick: GamepadStick) -> Vec2 {
+        let (x, y) = stick.axes();
+        Vec2::new(self.axis(x), self.axis(y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{GamepadBackend, PointerButton, VirtualGamepadBackend};
+    use glutin::event::VirtualKeyCode;
+
+    #[test]
+    fn composite_axis_is_normalized_and_actions_track_frames() {
+        let mut input_mgr = InputManager::new();
+        let key = |name| InputSource::from_name(name).unwrap();
+
+        input_mgr.apply_bindings(
+            serde_json::from_str(
+                r#"{
+                    "actions": { "jump": ["space", "mouse_left"] },
+                    "axes": { "move": { "left": ["a"], "right": ["d"], "down": ["s"], "up": ["w"] } }
+                }"#,
+            )
+            .unwrap(),
+        );
+
+        input_mgr.handle_source(key("w"), ElementState::Pressed);
+        input_mgr.handle_source(key("d"), ElementState::Pressed);
+
+        match input_mgr.axis_value("move") {
+            Some(AxisValue::Vector(value)) => {
+                assert!((value.x - value.y).abs() < 1e-6f32);
+                assert!((value.len() - 1f32).abs() < 1e-6f32);
+            }
+            value => panic!("unexpected axis value {:?}", value),
         }
+
+        input_mgr.handle_source(
+            InputSource::Key(VirtualKeyCode::Space),
+            ElementState::Pressed,
+        );
+        assert_eq!(input_mgr.just_pressed("jump"), Some(true));
+
+        input_mgr.end_frame();
+        input_mgr.handle_source(key("mouse_left"), ElementState::Pressed);
+        input_mgr.handle_source(key("space"), ElementState::Released);
+        assert_eq!(input_mgr.is_down("jump"), Some(true));
+        assert_eq!(input_mgr.just_released("jump"), Some(false));
+        assert_eq!(input_mgr.is_down("fire"), None);
+        assert_eq!(key("mouse_back"), InputSource::Mouse(PointerButton::Back));
+        assert_eq!(key("mouse_forward").name(), "mouse_forward");
+        assert_eq!(
+            key("mouse_12"),
+            InputSource::Mouse(PointerButton::Other(12))
+        );
+        assert_eq!(key("mouse_12").name(), "mouse_12");
+    }
+
+    #[test]
+    fn virtual_gamepads_feed_actions_and_axes() {
+        let mut input_mgr = InputManager::new();
+        let mut backend = VirtualGamepadBackend::new();
+        let gamepads = backend.gamepads();
+        let mut events = Vec::new();
+
+        input_mgr.apply_bindings(
+            serde_json::from_str(
+                r#"{
+                    "actions": { "jump": ["space", "gamepad_south"] },
+                    "axes": {
+                        "move": { "left": ["a"], "right": ["d"], "down": ["s"], "up": ["w"], "stick": "left" },
+                        "throttle": { "axis": "right_trigger" }
+                    }
+                }"#,
+            )
+            .unwrap(),
+        );
+
+        let first = gamepads.connect("first");
+        let second = gamepads.connect("second");
+        gamepads.press(first, GamepadButton::South);
+        gamepads.press(second, GamepadButton::South);
+        gamepads.set_axis(first, GamepadAxis::LeftStickX, 0.1f32);
+        gamepads.set_axis(second, GamepadAxis::RightTrigger, 1f32);
+        backend.poll(&mut events);
+
+        for event in events.drain(..) {
+            input_mgr.handle_gamepad_event(&event);
+        }
+
+        assert_eq!(input_mgr.gamepads().count(), 2);
+        assert_eq!(input_mgr.just_pressed("jump"), Some(true));
+        // Within the dead zone.
+        assert_eq!(
+            input_mgr.axis_value("move"),
+            Some(AxisValue::Vector(Vec2::default()))
+        );
+        assert_eq!(
+            input_mgr.axis_value("throttle"),
+            Some(AxisValue::Scalar(1f32))
+        );
+
+        input_mgr.end_frame();
+        gamepads.release(first, GamepadButton::South);
+        gamepads.set_axis(first, GamepadAxis::LeftStickX, -1f32);
+        gamepads.disconnect(second);
+        backend.poll(&mut events);
+
+        for event in events.drain(..) {
+            input_mgr.handle_gamepad_event(&event);
+        }
+
+        assert_eq!(input_mgr.just_released("jump"), Some(true));
+        assert_eq!(
+            input_mgr.axis_value("throttle"),
+            Some(AxisValue::Scalar(0f32))
+        );
+        assert_eq!(
+            input_mgr.axis_value("move"),
+            Some(AxisValue::Vector(Vec2::new(-1f32, 0f32)))
+        );
     }
 }
@@ -0,0 +1,62 @@
+use crate::input::{keycode_from_str, keycode_to_str, GamepadButton, PointerButton};
+use glutin::event::VirtualKeyCode;
+use mlua::prelude::*;
+use serde::{de::Error, Deserialize, Deserializer};
+
+// A physical key or button that actions and axes can be bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputSource {
+    Key(VirtualKeyCode),
+    Mouse(PointerButton),
+    // Held if it's held on any of the connected gamepads.
+    Gamepad(GamepadButton),
+}
+
+impl InputSource {
+    // Keys are named the same as in `KeyDown` events; mouse and gamepad buttons are prefixed with
+    // `mouse_` and `gamepad_` respectively.
+    pub fn from_name(name: &str) -> Option<Self> {
+        if let Some(button) = name.strip_prefix("mouse_") {
+            PointerButton::from_name(button).map(Self::Mouse)
+        } else if let Some(button) = name.strip_prefix("gamepad_") {
+            GamepadButton::from_name(button).map(Self::Gamepad)
+        } else {
+            keycode_from_str(name).map(Self::Key)
+        }
+    }
+
+    pub fn name(self) -> String {
+        match self {
+            Self::Key(keycode) => keycode_to_str(keycode).to_owned(),
+            Self::Mouse(PointerButton::Other(button)) => format!("mouse_{}", button),
+            Self::Mouse(button) => format!("mouse_{}", button.name()),
+            Self::Gamepad(button) => format!("gamepad_{}", button.name()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for InputSource {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let name = String::deserialize(deserializer)?;
+        Self::from_name(&name)
+            .ok_or_else(|| D::Error::custom(format!("{:?} is not a known key or button", name)))
+    }
+}
+
+impl<'lua> FromLua<'lua> for InputSource {
+    fn from_lua(value: LuaValue<'lua>, lua: &'lua Lua) -> LuaResult<Self> {
+        let str = String::from_lua(value, lua)?;
+        Self::from_name(&str).ok_or_else(|| {
+            format!("{:?} is invalid value for the type {}", str, "InputSource").to_lua_err()
+        })
+    }
+}
+
+impl<'lua> ToLua<'lua> for InputSource {
+    fn to_lua(self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        self.name().to_lua(lua)
+    }
+}
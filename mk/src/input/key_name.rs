@@ -0,0 +1,184 @@
+use glutin::event::VirtualKeyCode;
+
+macro_rules! key_names {
+    ($($keycode:ident => $name:literal,)*) => {
+        pub fn keycode_to_str(keycode: VirtualKeyCode) -> &'static str {
+            match keycode {
+                $(VirtualKeyCode::$keycode => $name,)*
+            }
+        }
+
+        pub fn keycode_from_str(name: &str) -> Option<VirtualKeyCode> {
+            match name {
+                $($name => Some(VirtualKeyCode::$keycode),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+key_names! {
+    Key1 => "key1",
+    Key2 => "key2",
+    Key3 => "key3",
+    Key4 => "key4",
+    Key5 => "key5",
+    Key6 => "key6",
+    Key7 => "key7",
+    Key8 => "key8",
+    Key9 => "key9",
+    Key0 => "key0",
+    A => "a",
+    B => "b",
+    C => "c",
+    D => "d",
+    E => "e",
+    F => "f",
+    G => "g",
+    H => "h",
+    I => "i",
+    J => "j",
+    K => "k",
+    L => "l",
+    M => "m",
+    N => "n",
+    O => "o",
+    P => "p",
+    Q => "q",
+    R => "r",
+    S => "s",
+    T => "t",
+    U => "u",
+    V => "v",
+    W => "w",
+    X => "x",
+    Y => "y",
+    Z => "z",
+    Escape => "escape",
+    F1 => "f1",
+    F2 => "f2",
+    F3 => "f3",
+    F4 => "f4",
+    F5 => "f5",
+    F6 => "f6",
+    F7 => "f7",
+    F8 => "f8",
+    F9 => "f9",
+    F10 => "f10",
+    F11 => "f11",
+    F12 => "f12",
+    F13 => "f13",
+    F14 => "f14",
+    F15 => "f15",
+    F16 => "f16",
+    F17 => "f17",
+    F18 => "f18",
+    F19 => "f19",
+    F20 => "f20",
+    F21 => "f21",
+    F22 => "f22",
+    F23 => "f23",
+    F24 => "f24",
+    Snapshot => "snapshot",
+    Scroll => "scroll",
+    Pause => "pause",
+    Insert => "insert",
+    Home => "home",
+    Delete => "delete",
+    End => "end",
+    PageDown => "page_down",
+    PageUp => "page_up",
+    Left => "left",
+    Up => "up",
+    Right => "right",
+    Down => "down",
+    Back => "back",
+    Return => "return",
+    Space => "space",
+    Compose => "compose",
+    Caret => "caret",
+    Numlock => "numlock",
+    Numpad0 => "numpad0",
+    Numpad1 => "numpad1",
+    Numpad2 => "numpad2",
+    Numpad3 => "numpad3",
+    Numpad4 => "numpad4",
+    Numpad5 => "numpad5",
+    Numpad6 => "numpad6",
+    Numpad7 => "numpad7",
+    Numpad8 => "numpad8",
+    Numpad9 => "numpad9",
+    NumpadAdd => "numpad_add",
+    NumpadDivide => "numpad_divide",
+    NumpadDecimal => "numpad_decimal",
+    NumpadComma => "numpad_comma",
+    NumpadEnter => "numpad_enter",
+    NumpadEquals => "numpad_equals",
+    NumpadMultiply => "numpad_multiply",
+    NumpadSubtract => "numpad_subtract",
+    AbntC1 => "abnt_c1",
+    AbntC2 => "abnt_c2",
+    Apostrophe => "apostrophe",
+    Apps => "apps",
+    Asterisk => "asterisk",
+    At => "at",
+    Ax => "ax",
+    Backslash => "backslash",
+    Calculator => "calculator",
+    Capital => "capital",
+    Colon => "colon",
+    Comma => "comma",
+    Convert => "convert",
+    Equals => "equals",
+    Grave => "grave",
+    Kana => "kana",
+    Kanji => "kanji",
+    LAlt => "l_alt",
+    LBracket => "l_bracket",
+    LControl => "l_control",
+    LShift => "l_shift",
+    LWin => "l_win",
+    Mail => "mail",
+    MediaSelect => "media_select",
+    MediaStop => "media_stop",
+    Minus => "minus",
+    Mute => "mute",
+    MyComputer => "my_computer",
+    NavigateForward => "navigate_forward",
+    NavigateBackward => "navigate_backward",
+    NextTrack => "next_track",
+    NoConvert => "no_convert",
+    OEM102 => "oem102",
+    Period => "period",
+    PlayPause => "play_pause",
+    Plus => "plus",
+    Power => "power",
+    PrevTrack => "prev_track",
+    RAlt => "r_alt",
+    RBracket => "r_bracket",
+    RControl => "r_control",
+    RShift => "r_shift",
+    RWin => "r_win",
+    Semicolon => "semicolon",
+    Slash => "slash",
+    Sleep => "sleep",
+    Stop => "stop",
+    Sysrq => "sysrq",
+    Tab => "tab",
+    Underline => "underline",
+    Unlabeled => "unlabeled",
+    VolumeDown => "volume_down",
+    VolumeUp => "volume_up",
+    Wake => "wake",
+    WebBack => "web_back",
+    WebFavorites => "web_favorites",
+    WebForward => "web_forward",
+    WebHome => "web_home",
+    WebRefresh => "web_refresh",
+    WebSearch => "web_search",
+    WebStop => "web_stop",
+    Yen => "yen",
+    Copy => "copy",
+    Paste => "paste",
+    Cut => "cut",
+}
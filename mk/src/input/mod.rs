@@ -1,7 +1,17 @@
-mod button;
+mod clipboard;
+mod gamepad;
+mod gamepad_backend;
+mod input_binding;
 mod input_manager;
-mod input_type;
+mod input_source;
+mod key_name;
+mod pointer_button;
 
-pub use button::*;
+pub use clipboard::*;
+pub use gamepad::*;
+pub use gamepad_backend::*;
+pub use input_binding::*;
 pub use input_manager::*;
-pub use input_type::*;
+pub use input_source::*;
+pub use key_name::*;
+pub use pointer_button::*;
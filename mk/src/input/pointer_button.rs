@@ -0,0 +1,113 @@
+use glutin::event::MouseButton;
+
+// A mouse button with the back and forward buttons told apart from the platform specific numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerButton {
+    Left,
+    Right,
+    Middle,
+    Back,
+    Forward,
+    Other(u16),
+}
+
+impl PointerButton {
+    // Other buttons are named by their platform specific numbers.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "left" => Some(Self::Left),
+            "right" => Some(Self::Right),
+            "middle" => Some(Self::Middle),
+            "back" => Some(Self::Back),
+            "forward" => Some(Self::Forward),
+            _ => name
+                .parse()
+                .ok()
+                .map(|button| Self::from(MouseButton::Other(button))),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Left => "left",
+            Self::Right => "right",
+            Self::Middle => "middle",
+            Self::Back => "back",
+            Self::Forward => "forward",
+            Self::Other(_) => "other",
+        }
+    }
+}
+
+impl From<MouseButton> for PointerButton {
+    fn from(button: MouseButton) -> Self {
+        match button {
+            MouseButton::Left => Self::Left,
+            MouseButton::Right => Self::Right,
+            MouseButton::Middle => Self::Middle,
+            MouseButton::Other(button) if BACK_BUTTONS.contains(&button) => Self::Back,
+            MouseButton::Other(button) if FORWARD_BUTTONS.contains(&button) => Self::Forward,
+            MouseButton::Other(button) => Self::Other(button),
+        }
+    }
+}
+
+// XBUTTON1 and XBUTTON2.
+#[cfg(target_os = "windows")]
+const BACK_BUTTONS: &[u16] = &[1];
+#[cfg(target_os = "windows")]
+const FORWARD_BUTTONS: &[u16] = &[2];
+
+// X11 buttons 8 and 9, and evdev's BTN_SIDE, BTN_EXTRA, BTN_BACK and BTN_FORWARD on Wayland.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+const BACK_BUTTONS: &[u16] = &[8, 0x113, 0x116];
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+const FORWARD_BUTTONS: &[u16] = &[9, 0x114, 0x115];
+
+// NSEvent's button numbers 3 and 4.
+#[cfg(target_os = "macos")]
+const BACK_BUTTONS: &[u16] = &[3];
+#[cfg(target_os = "macos")]
+const FORWARD_BUTTONS: &[u16] = &[4];
+
+// The DOM's buttons 3 and 4, which are reported as 0 and 1.
+#[cfg(target_arch = "wasm32")]
+const BACK_BUTTONS: &[u16] = &[0];
+#[cfg(target_arch = "wasm32")]
+const FORWARD_BUTTONS: &[u16] = &[1];
+
+// The other platforms don't report them separately.
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+    target_arch = "wasm32"
+)))]
+const BACK_BUTTONS: &[u16] = &[];
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+    target_arch = "wasm32"
+)))]
+const FORWARD_BUTTONS: &[u16] = &[];
@@ -19,7 +19,7 @@ pub mod transform;
 pub mod ui;
 pub mod util;
 
-pub use engine::run;
+pub use engine::{run, run_headless};
 pub use engine_context::{EngineContext, EngineContextWithoutSystemManager};
 pub use engine_error::EngineError;
 
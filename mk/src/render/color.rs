@@ -1,14 +1,20 @@
 use crate::codegen_traits::LuaApiTable;
 use mlua::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub struct Color {
     pub r: f32,
     pub g: f32,
     pub b: f32,
+    #[serde(default = "opaque_alpha")]
     pub a: f32,
 }
 
+fn opaque_alpha() -> f32 {
+    1f32
+}
+
 impl Color {
     pub fn new() -> Self {
         Self {
@@ -30,3 +30,4 @@ lua_rc!(Font as LuaRcFont);
 lua_rc!(Buffer as LuaRcBuffer);
 lua_rc!(Shader as LuaRcShader);
 lua_rc!(Texture as LuaRcTexture);
+lua_rc!(RenderTarget as LuaRcRenderTarget);
@@ -3,16 +3,14 @@ use crate::EngineContextWithoutSystemManager;
 
 pub struct RenderManager {
     buffer_pool: Vec<Buffer>,
-    common_shader_input_buffer: Buffer,
+    common_shader_input_buffer: Option<Buffer>,
 }
 
 impl RenderManager {
     pub fn new() -> Self {
         Self {
             buffer_pool: Vec::new(),
-            common_shader_input_buffer: Buffer::from_slice(&[
-                0f32, 0f32, 0f32, 0f32, 0f32, 0f32, 0f32, 0f32,
-            ]),
+            common_shader_input_buffer: None,
         }
     }
 
@@ -24,30 +22,38 @@ impl RenderManager {
         self.buffer_pool.push(buffer);
     }
 
-    pub fn update_uniforms(&self, context: &EngineContextWithoutSystemManager) {
+    pub fn update_uniforms(&mut self, context: &EngineContextWithoutSystemManager) {
         let time_mgr = context.time_mgr();
         let screen_mgr = context.screen_mgr();
 
-        self.common_shader_input_buffer.update(
-            0,
-            &[
-                time_mgr.dt(),
-                1f32 / time_mgr.dt(),
-                time_mgr.time().elapsed().as_secs_f32(),
-                1f32 / time_mgr.time().elapsed().as_secs_f32(),
-                screen_mgr.width() as f32,
-                screen_mgr.height() as f32,
-                1f32 / screen_mgr.width() as f32,
-                1f32 / screen_mgr.height() as f32,
-            ],
-        );
+        // The buffer is created lazily, so that the manager can be constructed without a GL context.
+        self.common_shader_input_buffer
+            .get_or_insert_with(|| {
+                Buffer::from_slice(&[0f32, 0f32, 0f32, 0f32, 0f32, 0f32, 0f32, 0f32])
+            })
+            .update(
+                0,
+                &[
+                    time_mgr.dt(),
+                    1f32 / time_mgr.dt(),
+                    time_mgr.time().elapsed().as_secs_f32(),
+                    1f32 / time_mgr.time().elapsed().as_secs_f32(),
+                    screen_mgr.width() as f32,
+                    screen_mgr.height() as f32,
+                    1f32 / screen_mgr.width() as f32,
+                    1f32 / screen_mgr.height() as f32,
+                ],
+            );
     }
 
     pub fn apply_common_shader_input(&self, shader: &Shader, req: &mut RenderRequest) {
         // TODO: Add shader type checking logic to alert if types have no match.
 
-        if let Some(uniform) = shader.uniform("Common") {
-            req.uniform_block(uniform.location, &self.common_shader_input_buffer);
+        if let (Some(uniform), Some(buffer)) = (
+            shader.uniform("Common"),
+            self.common_shader_input_buffer.as_ref(),
+        ) {
+            req.uniform_block(uniform.location, buffer);
         }
     }
 }
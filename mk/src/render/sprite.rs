@@ -1,10 +1,12 @@
 use crate::render::{LuaRcTexture, Texture};
 use codegen::LuaRc;
-use image::{open as open_image, ColorType, GenericImageView, ImageError};
+use image::ImageError;
 use mlua::prelude::*;
+use res::decoder::{decode_image, SpriteDecoderOutput};
+use res::SpriteChannel;
 use std::error::Error;
 use std::fmt::Display;
-use std::fs::metadata as fs_metadata;
+use std::fs::{metadata as fs_metadata, read as fs_read};
 use std::io::{Error as IOError, ErrorKind as IOErrorKind};
 use std::path::Path;
 use std::sync::Arc;
@@ -38,45 +40,14 @@ impl From<ImageError> for SpriteError {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum SpriteChannel {
-    R,
-    RG,
-    RGB,
-    RGBA,
-}
+pub fn texture_from_resource(resource: &SpriteDecoderOutput) -> Texture {
+    let (width, height, pixels) = (resource.width, resource.height, &resource.pixels);
 
-impl<'lua> FromLua<'lua> for SpriteChannel {
-    fn from_lua(value: LuaValue<'lua>, _lua: &'lua Lua) -> LuaResult<Self> {
-        match value {
-            LuaValue::String(channel) => match channel.to_str()? {
-                "r" => Ok(SpriteChannel::R),
-                "rg" => Ok(SpriteChannel::RG),
-                "rgb" => Ok(SpriteChannel::RGB),
-                "rgba" => Ok(SpriteChannel::RGBA),
-                _ => Err(format!(
-                    "{:?} is invalid value for the type {}",
-                    channel, "SpriteChannel",
-                )
-                .to_lua_err()),
-            },
-            _ => {
-                return Err(
-                    format!("the type {} must be a {}", "SpriteChannel", "string").to_lua_err(),
-                );
-            }
-        }
-    }
-}
-
-impl<'lua> ToLua<'lua> for SpriteChannel {
-    fn to_lua(self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
-        Ok(LuaValue::String(lua.create_string(match self {
-            SpriteChannel::R => "r",
-            SpriteChannel::RG => "rg",
-            SpriteChannel::RGB => "rgb",
-            SpriteChannel::RGBA => "rgba",
-        })?))
+    match resource.channel {
+        SpriteChannel::R => Texture::from_slice_r_u8(width, height, pixels),
+        SpriteChannel::RG => Texture::from_slice_rg_u8(width, height, pixels),
+        SpriteChannel::RGB => Texture::from_slice_rgb_u8(width, height, pixels),
+        SpriteChannel::RGBA => Texture::from_slice_rgba_u8(width, height, pixels),
     }
 }
 
@@ -139,6 +110,7 @@ impl<'lua> ToLua<'lua> for TexelMapping {
 
 #[derive(LuaRc, Debug)]
 pub struct Sprite {
+    #[lua_userfunc(get=lua_get_channel)]
     channel: SpriteChannel,
     #[lua_userdata(LuaRcTexture)]
     texture: Arc<Texture>,
@@ -168,53 +140,19 @@ impl Sprite {
             }
         }
 
-        let image = open_image(image_path?)?;
-        let (width, height) = image.dimensions();
-        let (channel, texture) = match channel {
-            Some(channel) => match channel {
-                SpriteChannel::R => (
-                    SpriteChannel::R,
-                    Texture::from_slice_r_u8(width, height, image.to_luma8().as_raw()),
-                ),
-                SpriteChannel::RG => (
-                    SpriteChannel::RG,
-                    Texture::from_slice_rg_u8(width, height, image.to_luma_alpha8().as_raw()),
-                ),
-                SpriteChannel::RGB => (
-                    SpriteChannel::RGB,
-                    Texture::from_slice_rgb_u8(width, height, image.to_rgb8().as_raw()),
-                ),
-                SpriteChannel::RGBA => (
-                    SpriteChannel::RGBA,
-                    Texture::from_slice_rgba_u8(width, height, image.to_rgba8().as_raw()),
-                ),
-            },
-            None => match image.color() {
-                ColorType::L8 | ColorType::L16 => (
-                    SpriteChannel::R,
-                    Texture::from_slice_r_u8(width, height, image.to_luma8().as_raw()),
-                ),
-                ColorType::La8 | ColorType::La16 => (
-                    SpriteChannel::RG,
-                    Texture::from_slice_rg_u8(width, height, image.to_luma_alpha8().as_raw()),
-                ),
-                ColorType::Rgb8 | ColorType::Rgb16 => (
-                    SpriteChannel::RGB,
-                    Texture::from_slice_rgb_u8(width, height, image.to_rgb8().as_raw()),
-                ),
-                ColorType::Rgba8 | ColorType::Rgba16 => (
-                    SpriteChannel::RGBA,
-                    Texture::from_slice_rgba_u8(width, height, image.to_rgba8().as_raw()),
-                ),
-                _ => unreachable!(),
-            },
-        };
+        Self::from_memory(&fs_read(image_path?)?, channel)
+    }
 
-        Ok(Self {
-            channel,
-            texture: texture.into(),
-            texel_mapping: TexelMapping::new((0, 0), (width, height)),
-        })
+    pub fn from_memory(image: &[u8], channel: Option<SpriteChannel>) -> Result<Self, SpriteError> {
+        Ok(Self::from_resource(&decode_image(image, channel)?))
+    }
+
+    pub fn from_resource(resource: &SpriteDecoderOutput) -> Self {
+        Self {
+            channel: resource.channel,
+            texture: texture_from_resource(resource).into(),
+            texel_mapping: TexelMapping::new((0, 0), (resource.width, resource.height)),
+        }
     }
 
     pub fn from_atlas(texture: Arc<Texture>, texel_mapping: TexelMapping) -> Self {
@@ -264,4 +202,8 @@ impl Sprite {
             lhs - rhs
         }
     }
+
+    fn lua_get_channel<'lua>(&self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        self.channel.to_str().to_lua(lua)
+    }
 }
@@ -1,11 +1,15 @@
-use crate::render::{LuaRcSprite, LuaRcTexture, Sprite, SpriteChannel, TexelMapping, Texture};
+use crate::render::{
+    texture_from_resource, LuaRcSprite, LuaRcTexture, Sprite, TexelMapping, Texture,
+};
 use codegen::LuaRc;
-use image::{open as open_image, ColorType, GenericImageView, ImageError};
+use image::ImageError;
+use res::decoder::{decode_image, SpriteDecoderOutput};
+use res::SpriteChannel;
 use serde::Deserialize;
 use serde_json::{from_str, Error as JSONError};
 use std::error::Error;
 use std::fmt::Display;
-use std::fs::{metadata as fs_metadata, read_to_string};
+use std::fs::{metadata as fs_metadata, read as fs_read, read_to_string};
 use std::io::{Error as IOError, ErrorKind as IOErrorKind};
 use std::path::Path;
 use std::sync::Arc;
@@ -102,42 +106,32 @@ impl SpriteNinePatch {
             }
         }
 
-        let image = open_image(image_path?)?;
-        let (width, height) = image.dimensions();
-        let texture = Arc::new(match channel {
-            Some(channel) => match channel {
-                SpriteChannel::R => {
-                    Texture::from_slice_r_u8(width, height, image.to_luma8().as_raw())
-                }
-                SpriteChannel::RG => {
-                    Texture::from_slice_rg_u8(width, height, image.to_luma_alpha8().as_raw())
-                }
-                SpriteChannel::RGB => {
-                    Texture::from_slice_rgb_u8(width, height, image.to_rgb8().as_raw())
-                }
-                SpriteChannel::RGBA => {
-                    Texture::from_slice_rgba_u8(width, height, image.to_rgba8().as_raw())
-                }
-            },
-            None => match image.color() {
-                ColorType::L8 | ColorType::L16 => {
-                    Texture::from_slice_r_u8(width, height, image.to_luma8().as_raw())
-                }
-                ColorType::La8 | ColorType::La16 => {
-                    Texture::from_slice_rg_u8(width, height, image.to_luma_alpha8().as_raw())
-                }
-                ColorType::Rgb8 | ColorType::Rgb16 => {
-                    Texture::from_slice_rgb_u8(width, height, image.to_rgb8().as_raw())
-                }
-                ColorType::Rgba8 | ColorType::Rgba16 => {
-                    Texture::from_slice_rgba_u8(width, height, image.to_rgba8().as_raw())
-                }
-                _ => unreachable!(),
-            },
-        });
+        let metadata = read_to_string(path.as_ref().with_extension("meta.json"))?;
+
+        Self::from_memory(&fs_read(image_path?)?, &metadata, channel)
+    }
+
+    pub fn from_memory(
+        image: &[u8],
+        metadata: &str,
+        channel: Option<SpriteChannel>,
+    ) -> Result<Self, SpriteNinePatchError> {
+        Self::from_resource(&decode_image(image, channel)?, metadata)
+    }
 
-        let metadata: NinePatchMetadataJSON =
-            from_str(&read_to_string(path.as_ref().with_extension("meta.json"))?)?;
+    pub fn from_resource(
+        resource: &SpriteDecoderOutput,
+        metadata: &str,
+    ) -> Result<Self, SpriteNinePatchError> {
+        Self::from_texture(Arc::new(texture_from_resource(resource)), metadata)
+    }
+
+    pub fn from_texture(
+        texture: Arc<Texture>,
+        metadata: &str,
+    ) -> Result<Self, SpriteNinePatchError> {
+        let (width, height) = (texture.width(), texture.height());
+        let metadata: NinePatchMetadataJSON = from_str(metadata)?;
 
         let sprite_lt = Sprite::from_atlas(
             texture.clone(),
@@ -1,9 +1,10 @@
 use crate::codegen_traits::LuaApiTable;
 use codegen::LuaStruct;
 use mlua::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::ops::{Div, DivAssign, Mul, MulAssign, Neg};
 
-#[derive(LuaStruct, Default, Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[derive(LuaStruct, Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub struct Size {
     pub width: f32,
     pub height: f32,
@@ -1,9 +1,10 @@
 use crate::codegen_traits::LuaApiTable;
 use codegen::LuaStruct;
 use mlua::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
-#[derive(LuaStruct, Default, Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[derive(LuaStruct, Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub struct Vec2 {
     pub x: f32,
     pub y: f32,
@@ -0,0 +1,30 @@
+use crate::component::*;
+use crate::time::TimeManager;
+use legion::*;
+use std::sync::Arc;
+
+// Returns the entities whose flipbook has looped or finished during this frame.
+pub fn animate_flipbooks(
+    world: &mut World,
+    time_mgr: &TimeManager,
+) -> Vec<(Entity, FlipbookAdvance)> {
+    let mut advances = Vec::new();
+
+    for (&entity, flipbook, sprite_renderer) in
+        <(Entity, &mut FlipbookAnimator, Option<&mut SpriteRenderer>)>::query().iter_mut(world)
+    {
+        let advance = flipbook.advance(time_mgr.dt());
+
+        if advance.looped || advance.finished {
+            advances.push((entity, advance));
+        }
+
+        if let (Some(sprite_renderer), Some(sprite)) = (sprite_renderer, flipbook.sprite()) {
+            if !Arc::ptr_eq(&sprite_renderer.sprite, sprite) {
+                sprite_renderer.sprite = sprite.clone();
+            }
+        }
+    }
+
+    advances
+}
@@ -1,172 +1,468 @@
-use crate::animation::{AnimationKeyFrame, AnimationTimeLine, Interpolatable};
+use crate::animation::{Animation, AnimationEvent, AnimationKeyFrame, AnimationTimeLine};
+use crate::codegen_traits::Animate;
 use crate::component::*;
+use crate::emit_diagnostic_warn;
 use crate::time::TimeManager;
 use crate::transform::TransformManager;
+use crate::ui::UIManager;
+use legion::storage::Component;
+use legion::world::EntryMut;
 use legion::*;
 
+#[derive(Debug, Clone)]
+pub enum AnimationNotification {
+    Event(AnimationEvent),
+    Loop,
+    Finish,
+    StateChange { from: String, to: String },
+}
+
+// Returns the notifications to be emitted to each entity, in the order they occurred.
 pub fn animate_sigle_animations(
     world: &mut World,
     time_mgr: &TimeManager,
     transform_mgr: &mut TransformManager,
-) {
+    ui_mgr: &mut UIManager,
+) -> Vec<(Entity, AnimationNotification)> {
+    let mut notifications = Vec::new();
     let mut query = <(Entity, &mut SingleAnimator)>::query();
     let (mut world, mut rest) = world.split_for_query(&query);
 
     for (&entity, animator) in query.iter_mut(&mut world) {
-        let animation = if let Some(animation) = &mut animator.animation {
-            animation
+        let animation = if let Some(animation) = &animator.animation {
+            animation.clone()
         } else {
             continue;
         };
 
-        let in_reverse = animation.pingpong && animator.is_pong;
+        let step = advance_playback(
+            &animation,
+            &mut animator.time,
+            &mut animator.is_pong,
+            time_mgr.dt() * animator.speed,
+            entity,
+            &mut notifications,
+        );
 
-        if in_reverse {
-            animator.time -= time_mgr.dt() * animator.speed;
-        } else {
-            animator.time += time_mgr.dt() * animator.speed;
-        }
-
-        let normalized_time = animator.time / animation.duration;
-
-        for time_line in &animation.time_lines {
-            let key_frame =
-                if let Some(key_frame) = find_key_frame(time_line, normalized_time, in_reverse) {
-                    key_frame
-                } else {
-                    continue;
-                };
-            let normalized_time_in_key_frame =
-                (normalized_time - key_frame.begin) / (key_frame.end - key_frame.begin);
+        for (index, time_line) in animation.time_lines.iter().enumerate() {
+            if animator.invalid_time_lines.contains(&index) {
+                continue;
+            }
 
-            let transform = if let Ok(&transform) =
-                rest.entry_ref(entity).unwrap().get_component::<Transform>()
+            let (key_frame, normalized_time_in_key_frame) = if let Some(key_frame) =
+                find_key_frame(time_line, step.normalized_time, step.in_reverse)
             {
-                transform.index()
-            } else {
-                continue;
-            };
-            let child_transform = if let Some(transform) = transform_mgr.find_child_by_name(
-                transform,
-                time_line
-                    .transform
-                    .as_ref()
-                    .map(|transform| transform.as_slice()),
-            ) {
-                transform
-            } else {
-                continue;
-            };
-            let target_entity = transform_mgr.entity(child_transform);
-            let target_entry = if let Ok(entry) = rest.entry_mut(target_entity) {
-                entry
+                key_frame
             } else {
                 continue;
             };
+            let application = apply_time_line(
+                &mut rest,
+                transform_mgr,
+                ui_mgr,
+                entity,
+                time_line,
+                key_frame,
+                key_frame.easing.ease(normalized_time_in_key_frame),
+            );
 
-            match time_line.component.as_str() {
-                "transform" => {
-                    let transform =
-                        if let Ok(&transform) = target_entry.get_component::<Transform>() {
-                            transform
-                        } else {
-                            continue;
-                        };
-                    let transform = transform_mgr.transform_mut(transform.index());
-
-                    match time_line.field.as_str() {
-                        "position.x" => {
-                            transform.position.x = <f64 as Interpolatable>::interpolate(
-                                key_frame.from.as_float(),
-                                key_frame.to.as_float(),
-                                normalized_time_in_key_frame,
-                            ) as _;
-                            transform.mark_as_dirty();
-                        }
-                        "position.y" => {
-                            transform.position.y = <f64 as Interpolatable>::interpolate(
-                                key_frame.from.as_float(),
-                                key_frame.to.as_float(),
-                                normalized_time_in_key_frame,
-                            ) as _;
-                            transform.mark_as_dirty();
-                        }
-                        "scale.x" => {
-                            transform.scale.x = <f64 as Interpolatable>::interpolate(
-                                key_frame.from.as_float(),
-                                key_frame.to.as_float(),
-                                normalized_time_in_key_frame,
-                            ) as _;
-                            transform.mark_as_dirty();
-                        }
-                        "scale.y" => {
-                            transform.scale.y = <f64 as Interpolatable>::interpolate(
-                                key_frame.from.as_float(),
-                                key_frame.to.as_float(),
-                                normalized_time_in_key_frame,
-                            ) as _;
-                            transform.mark_as_dirty();
-                        }
-                        "angle" => {
-                            transform.angle = <f64 as Interpolatable>::interpolate(
-                                key_frame.from.as_float(),
-                                key_frame.to.as_float(),
-                                normalized_time_in_key_frame,
-                            ) as _;
-                            transform.mark_as_dirty();
-                        }
-                        _ => {}
-                    }
-                }
-                _ => {}
+            if application.is_invalid() && animator.invalid_time_lines.insert(index) {
+                application.report(time_line);
             }
         }
 
-        if in_reverse {
-            if animator.time <= 0f32 {
-                animator.time = 0f32;
-                animator.is_pong = false;
+        if step.finished {
+            animator.animation = None;
+        }
+    }
+
+    notifications
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct PlaybackStep {
+    // The normalized time the time lines should be evaluated at, before wrapping around.
+    pub normalized_time: f32,
+    pub in_reverse: bool,
+    pub finished: bool,
+}
+
+// Advances the playback by the given (already scaled) delta, pushing the crossed markers and the loop or finish notification.
+pub(crate) fn advance_playback(
+    animation: &Animation,
+    time: &mut f32,
+    is_pong: &mut bool,
+    delta: f32,
+    entity: Entity,
+    notifications: &mut Vec<(Entity, AnimationNotification)>,
+) -> PlaybackStep {
+    let in_reverse = animation.pingpong && *is_pong;
+    let previous_time = *time;
+
+    if in_reverse {
+        *time -= delta;
+    } else {
+        *time += delta;
+    }
+
+    let normalized_time = *time / animation.duration;
+    let reached_start = in_reverse && *time <= 0f32;
+    let reached_end = !in_reverse && animation.duration <= *time;
+    let previous_normalized_time = previous_time / animation.duration;
 
-                if !animation.looping {
-                    animator.animation = None;
+    // Ranges are half-open in the direction of travel so that a marker is never emitted twice.
+    // The closing bound is included only if the playback is not going to cross it again.
+    for event in &animation.events {
+        let crossed = if in_reverse {
+            event.time <= previous_normalized_time
+                && if reached_start {
+                    0f32 < event.time || !animation.looping
+                } else {
+                    normalized_time < event.time
                 }
-            }
         } else {
-            if animation.duration <= animator.time {
-                if animation.pingpong {
-                    animator.time = animation.duration;
-                    animator.is_pong = true;
+            previous_normalized_time <= event.time
+                && if reached_end {
+                    event.time < 1f32 || !animation.pingpong
                 } else {
-                    animator.time = 0f32;
-                    animator.is_pong = false;
-
-                    if !animation.looping {
-                        animator.animation = None;
-                    }
+                    event.time < normalized_time
                 }
+        };
+
+        if crossed {
+            notifications.push((entity, AnimationNotification::Event(event.clone())));
+        }
+    }
+
+    let mut finished = false;
+
+    if reached_start {
+        *time = 0f32;
+        *is_pong = false;
+
+        if animation.looping {
+            notifications.push((entity, AnimationNotification::Loop));
+        } else {
+            finished = true;
+            notifications.push((entity, AnimationNotification::Finish));
+        }
+    } else if reached_end {
+        if animation.pingpong {
+            *time = animation.duration;
+            *is_pong = true;
+        } else {
+            *time = 0f32;
+            *is_pong = false;
+
+            if animation.looping {
+                notifications.push((entity, AnimationNotification::Loop));
+            } else {
+                finished = true;
+                notifications.push((entity, AnimationNotification::Finish));
             }
         }
     }
+
+    PlaybackStep {
+        normalized_time,
+        in_reverse,
+        finished,
+    }
 }
 
-fn find_key_frame(
-    time_line: &AnimationTimeLine,
-    normalized_time: f32,
-    in_reverse: bool,
-) -> Option<&AnimationKeyFrame> {
-    if in_reverse {
-        for key_frame in time_line.key_frames.iter() {
-            if normalized_time <= key_frame.end {
-                return Some(key_frame);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum TimeLineApplication {
+    Applied,
+    // The target entity or its component is missing for now; the time line may apply later.
+    Skipped,
+    UnknownComponent,
+    UnknownField,
+}
+
+impl TimeLineApplication {
+    // The field is unknown if the component has not animated it.
+    fn from_animated(animated: bool) -> Self {
+        if animated {
+            Self::Applied
+        } else {
+            Self::UnknownField
+        }
+    }
+
+    pub fn is_invalid(self) -> bool {
+        matches!(self, Self::UnknownComponent | Self::UnknownField)
+    }
+
+    pub fn report(self, time_line: &AnimationTimeLine) {
+        match self {
+            Self::UnknownComponent => {
+                emit_diagnostic_warn!(format!(
+                    "animation time line targets an unknown component '{}'.",
+                    time_line.component
+                ));
+            }
+            Self::UnknownField => {
+                emit_diagnostic_warn!(format!(
+                    "component '{}' has no animatable field '{}'.",
+                    time_line.component, time_line.field
+                ));
             }
+            _ => {}
         }
+    }
+}
+
+// Applies the key frame at the eased normalized time to the component the time line targets, relative to the entity.
+pub(crate) fn apply_time_line(
+    rest: &mut impl EntityStore,
+    transform_mgr: &mut TransformManager,
+    ui_mgr: &mut UIManager,
+    entity: Entity,
+    time_line: &AnimationTimeLine,
+    key_frame: &AnimationKeyFrame,
+    normalized_time_in_key_frame: f32,
+) -> TimeLineApplication {
+    let transform =
+        if let Ok(&transform) = rest.entry_ref(entity).unwrap().get_component::<Transform>() {
+            transform.index()
+        } else {
+            return TimeLineApplication::Skipped;
+        };
+    let child_transform = if let Some(transform) = transform_mgr.find_child_by_name(
+        transform,
+        time_line
+            .transform
+            .as_ref()
+            .map(|transform| transform.as_slice()),
+    ) {
+        transform
+    } else {
+        return TimeLineApplication::Skipped;
+    };
+    let target_entity = transform_mgr.entity(child_transform);
+    let target_entry = if let Ok(entry) = rest.entry_mut(target_entity) {
+        entry
     } else {
-        for key_frame in time_line.key_frames.iter().rev() {
-            if key_frame.begin <= normalized_time {
-                return Some(key_frame);
+        return TimeLineApplication::Skipped;
+    };
+
+    match time_line.component.as_str() {
+        "transform" => {
+            let transform = if let Ok(&transform) = target_entry.get_component::<Transform>() {
+                transform
+            } else {
+                return TimeLineApplication::Skipped;
+            };
+            let transform = transform_mgr.transform_mut(transform.index());
+            let animated = transform.animate(time_line, key_frame, normalized_time_in_key_frame);
+
+            if animated {
+                transform.mark_as_dirty();
+            }
+
+            TimeLineApplication::from_animated(animated)
+        }
+        "ui_element" => {
+            let element = if let Ok(&element) = target_entry.get_component::<UIElement>() {
+                element
+            } else {
+                return TimeLineApplication::Skipped;
+            };
+            let element = ui_mgr.element_mut(element.index());
+            let animated = element.animate(time_line, key_frame, normalized_time_in_key_frame);
+
+            if animated {
+                element.mark_as_dirty();
             }
+
+            TimeLineApplication::from_animated(animated)
+        }
+        "size" => animate_component::<Size>(
+            target_entry,
+            time_line,
+            key_frame,
+            normalized_time_in_key_frame,
+        ),
+        "camera" => animate_component::<Camera>(
+            target_entry,
+            time_line,
+            key_frame,
+            normalized_time_in_key_frame,
+        ),
+        "glyph_renderer" => animate_component::<GlyphRenderer>(
+            target_entry,
+            time_line,
+            key_frame,
+            normalized_time_in_key_frame,
+        ),
+        "nine_patch_renderer" => animate_component::<NinePatchRenderer>(
+            target_entry,
+            time_line,
+            key_frame,
+            normalized_time_in_key_frame,
+        ),
+        "sprite_renderer" => animate_component::<SpriteRenderer>(
+            target_entry,
+            time_line,
+            key_frame,
+            normalized_time_in_key_frame,
+        ),
+        "tilemap_renderer" => animate_component::<TilemapRenderer>(
+            target_entry,
+            time_line,
+            key_frame,
+            normalized_time_in_key_frame,
+        ),
+        "ui_scaler" => animate_component::<UIScaler>(
+            target_entry,
+            time_line,
+            key_frame,
+            normalized_time_in_key_frame,
+        ),
+        _ => TimeLineApplication::UnknownComponent,
+    }
+}
+
+// The time line is skipped if the entity has no such component, as the component may be added later.
+fn animate_component<T>(
+    mut entry: EntryMut,
+    time_line: &AnimationTimeLine,
+    key_frame: &AnimationKeyFrame,
+    normalized_time_in_key_frame: f32,
+) -> TimeLineApplication
+where
+    T: Component + Animate,
+{
+    match entry.get_component_mut::<T>() {
+        Ok(component) => TimeLineApplication::from_animated(component.animate(
+            time_line,
+            key_frame,
+            normalized_time_in_key_frame,
+        )),
+        Err(..) => TimeLineApplication::Skipped,
+    }
+}
+
+// Returns the key frame at the normalized time and the (uneased) normalized time within it.
+pub(crate) fn find_key_frame(
+    time_line: &AnimationTimeLine,
+    normalized_time: f32,
+    in_reverse: bool,
+) -> Option<(&AnimationKeyFrame, f32)> {
+    let key_frame = if in_reverse {
+        time_line
+            .key_frames
+            .iter()
+            .find(|key_frame| normalized_time <= key_frame.end)
+    } else {
+        time_line
+            .key_frames
+            .iter()
+            .rev()
+            .find(|key_frame| key_frame.begin <= normalized_time)
+    }?;
+
+    Some((
+        key_frame,
+        (normalized_time - key_frame.begin) / (key_frame.end - key_frame.begin),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn animation(looping: bool, pingpong: bool) -> Animation {
+        Animation {
+            time_lines: Vec::new(),
+            events: [0f32, 0.5f32, 1f32]
+                .iter()
+                .map(|&time| AnimationEvent {
+                    time,
+                    name: time.to_string(),
+                    payload: None,
+                })
+                .collect(),
+            duration: 1f32,
+            looping,
+            pingpong,
         }
     }
 
-    None
+    // Advances by a quarter of the duration per step, so the marker at 0.5 lies on a step boundary.
+    fn play(animation: &Animation, steps: usize) -> Vec<Vec<String>> {
+        let entity = World::default().push(());
+        let (mut time, mut is_pong) = (0f32, false);
+
+        (0..steps)
+            .map(|_| {
+                let mut notifications = Vec::new();
+                advance_playback(
+                    animation,
+                    &mut time,
+                    &mut is_pong,
+                    0.25f32,
+                    entity,
+                    &mut notifications,
+                );
+
+                notifications
+                    .into_iter()
+                    .map(|(_, notification)| match notification {
+                        AnimationNotification::Event(event) => event.name,
+                        AnimationNotification::Loop => "loop".to_owned(),
+                        AnimationNotification::Finish => "finish".to_owned(),
+                        AnimationNotification::StateChange { .. } => unreachable!(),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn forward_playback_emits_every_marker_once_and_finishes() {
+        assert_eq!(
+            play(&animation(false, false), 4),
+            vec![vec!["0"], vec![], vec!["0.5"], vec!["1", "finish"],]
+        );
+    }
+
+    #[test]
+    fn looping_playback_emits_the_markers_on_every_cycle() {
+        assert_eq!(
+            play(&animation(true, false), 5),
+            vec![vec!["0"], vec![], vec!["0.5"], vec!["1", "loop"], vec!["0"],]
+        );
+    }
+
+    #[test]
+    fn pingpong_playback_emits_the_markers_in_both_directions() {
+        assert_eq!(
+            play(&animation(true, true), 9),
+            vec![
+                vec!["0"],
+                vec![],
+                vec!["0.5"],
+                vec![],
+                vec!["1"],
+                vec![],
+                vec!["0.5"],
+                vec!["loop"],
+                vec!["0"],
+            ]
+        );
+        assert_eq!(
+            play(&animation(false, true), 8),
+            vec![
+                vec!["0"],
+                vec![],
+                vec!["0.5"],
+                vec![],
+                vec!["1"],
+                vec![],
+                vec!["0.5"],
+                vec!["0", "finish"],
+            ]
+        );
+    }
 }
@@ -0,0 +1,398 @@
+use crate::animation::{AnimationEasing, AnimationKeyFrame, AnimationTimeLine};
+use crate::component::*;
+use crate::system::{
+    advance_playback, apply_time_line, find_key_frame, AnimationNotification, PlaybackStep,
+};
+use crate::time::TimeManager;
+use crate::transform::TransformManager;
+use crate::ui::UIManager;
+use legion::*;
+use std::collections::{HashMap, HashSet};
+
+// Returns the notifications to be emitted to each entity, in the order they occurred.
+pub fn animate_state_machines(
+    world: &mut World,
+    time_mgr: &TimeManager,
+    transform_mgr: &mut TransformManager,
+    ui_mgr: &mut UIManager,
+) -> Vec<(Entity, AnimationNotification)> {
+    let mut notifications = Vec::new();
+    let mut query = <(Entity, &mut StateMachineAnimator)>::query();
+    let (mut world, mut rest) = world.split_for_query(&query);
+
+    for (&entity, animator) in query.iter_mut(&mut world) {
+        animator.evaluate_transitions();
+
+        for (from, to) in animator.state_changes.drain(..) {
+            notifications.push((entity, AnimationNotification::StateChange { from, to }));
+        }
+
+        let dt = time_mgr.dt() * animator.speed;
+        let state = if let Some(state) = animator.states.get(&animator.current.state) {
+            state.clone()
+        } else {
+            continue;
+        };
+        let step = step_state(
+            &state,
+            &mut animator.current,
+            dt,
+            entity,
+            &mut notifications,
+        );
+
+        // The state being faded out keeps playing, but its markers are no longer notified.
+        let fading = match &mut animator.crossfade {
+            Some(crossfade) => match animator.states.get(&crossfade.playback.state) {
+                Some(fading_state) => {
+                    let fading_state = fading_state.clone();
+                    let fading_step = step_state(
+                        &fading_state,
+                        &mut crossfade.playback,
+                        dt,
+                        entity,
+                        &mut Vec::new(),
+                    );
+                    crossfade.elapsed += dt;
+
+                    Some((
+                        crossfade.playback.state.clone(),
+                        fading_state,
+                        fading_step,
+                        crossfade.weight(),
+                    ))
+                }
+                None => None,
+            },
+            None => None,
+        };
+
+        for (index, time_line) in state.animation.time_lines.iter().enumerate() {
+            if is_invalid(&animator.invalid_time_lines, &animator.current.state, index) {
+                continue;
+            }
+
+            let (key_frame, normalized_time_in_key_frame) = if let Some(key_frame) =
+                find_key_frame(time_line, step.normalized_time, step.in_reverse)
+            {
+                key_frame
+            } else {
+                continue;
+            };
+
+            // Blends from the faded out value when both states animate the same field.
+            let blended = fading
+                .as_ref()
+                .and_then(|(_, fading_state, fading_step, _)| {
+                    let fading_time_line = fading_state
+                        .animation
+                        .time_lines
+                        .iter()
+                        .find(|fading_time_line| is_same_target(fading_time_line, time_line))?;
+                    let (fading_key_frame, fading_normalized_time_in_key_frame) = find_key_frame(
+                        fading_time_line,
+                        fading_step.normalized_time,
+                        fading_step.in_reverse,
+                    )?;
+
+                    Some(AnimationKeyFrame {
+                        begin: 0f32,
+                        end: 1f32,
+                        from: fading_key_frame.sample(fading_normalized_time_in_key_frame),
+                        to: key_frame.sample(normalized_time_in_key_frame),
+                        easing: AnimationEasing::Linear,
+                        color_space: key_frame.color_space,
+                    })
+                });
+            let application = match (&blended, &fading) {
+                (Some(blended), Some((_, _, _, weight))) => apply_time_line(
+                    &mut rest,
+                    transform_mgr,
+                    ui_mgr,
+                    entity,
+                    time_line,
+                    blended,
+                    *weight,
+                ),
+                _ => apply_time_line(
+                    &mut rest,
+                    transform_mgr,
+                    ui_mgr,
+                    entity,
+                    time_line,
+                    key_frame,
+                    key_frame.easing.ease(normalized_time_in_key_frame),
+                ),
+            };
+
+            if application.is_invalid()
+                && animator
+                    .invalid_time_lines
+                    .entry(animator.current.state.clone())
+                    .or_default()
+                    .insert(index)
+            {
+                application.report(time_line);
+            }
+        }
+
+        if let Some((fading_state_name, fading_state, fading_step, weight)) = &fading {
+            // Fields only the faded out state animates keep its pose until the crossfade ends.
+            for (index, time_line) in fading_state.animation.time_lines.iter().enumerate() {
+                if is_invalid(&animator.invalid_time_lines, fading_state_name, index)
+                    || state
+                        .animation
+                        .time_lines
+                        .iter()
+                        .any(|current| is_same_target(current, time_line))
+                {
+                    continue;
+                }
+
+                let (key_frame, normalized_time_in_key_frame) = if let Some(key_frame) =
+                    find_key_frame(
+                        time_line,
+                        fading_step.normalized_time,
+                        fading_step.in_reverse,
+                    ) {
+                    key_frame
+                } else {
+                    continue;
+                };
+                let application = apply_time_line(
+                    &mut rest,
+                    transform_mgr,
+                    ui_mgr,
+                    entity,
+                    time_line,
+                    key_frame,
+                    key_frame.easing.ease(normalized_time_in_key_frame),
+                );
+
+                if application.is_invalid()
+                    && animator
+                        .invalid_time_lines
+                        .entry(fading_state_name.clone())
+                        .or_default()
+                        .insert(index)
+                {
+                    application.report(time_line);
+                }
+            }
+
+            if 1f32 <= *weight {
+                animator.crossfade = None;
+            }
+        }
+    }
+
+    notifications
+}
+
+fn step_state(
+    state: &AnimatorState,
+    playback: &mut AnimatorPlayback,
+    dt: f32,
+    entity: Entity,
+    notifications: &mut Vec<(Entity, AnimationNotification)>,
+) -> PlaybackStep {
+    let duration = state.animation.duration;
+
+    if playback.finished {
+        return PlaybackStep {
+            normalized_time: playback.time / duration,
+            in_reverse: false,
+            finished: true,
+        };
+    }
+
+    let delta = dt * state.speed;
+    let step = advance_playback(
+        &state.animation,
+        &mut playback.time,
+        &mut playback.is_pong,
+        delta,
+        entity,
+        notifications,
+    );
+
+    playback.progress += delta.abs() / duration;
+
+    if step.finished {
+        playback.finished = true;
+        playback.time = if step.in_reverse { 0f32 } else { duration };
+    }
+
+    step
+}
+
+fn is_invalid(
+    invalid_time_lines: &HashMap<String, HashSet<usize>>,
+    state: &str,
+    index: usize,
+) -> bool {
+    invalid_time_lines
+        .get(state)
+        .is_some_and(|indices| indices.contains(&index))
+}
+
+fn is_same_target(lhs: &AnimationTimeLine, rhs: &AnimationTimeLine) -> bool {
+    lhs.transform == rhs.transform && lhs.component == rhs.component && lhs.field == rhs.field
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::{Animation, AnimationValue};
+    use std::sync::Arc;
+
+    fn state(x: f64) -> AnimatorState {
+        let time_line = AnimationTimeLine {
+            key_frames: vec![AnimationKeyFrame {
+                begin: 0f32,
+                end: 1f32,
+                from: AnimationValue::Float(x),
+                to: AnimationValue::Float(x),
+                easing: Default::default(),
+                color_space: Default::default(),
+            }],
+            transform: None,
+            component: "transform".to_owned(),
+            field: "position.x".to_owned(),
+        };
+
+        AnimatorState {
+            animation: Arc::new(Animation {
+                time_lines: vec![time_line],
+                events: Vec::new(),
+                duration: 1f32,
+                looping: true,
+                pingpong: false,
+            }),
+            speed: 1f32,
+        }
+    }
+
+    struct Fixture {
+        world: World,
+        time_mgr: TimeManager,
+        transform_mgr: TransformManager,
+        ui_mgr: UIManager,
+        entity: Entity,
+        transform: u32,
+    }
+
+    impl Fixture {
+        fn new(animator: StateMachineAnimator) -> Self {
+            let mut world = World::default();
+            let mut time_mgr = TimeManager::new();
+            let mut transform_mgr = TransformManager::new();
+            let entity = world.push(());
+            let transform = transform_mgr.alloc(entity);
+            let mut entry = world.entry(entity).unwrap();
+            entry.add_component(Transform::new(transform));
+            entry.add_component(animator);
+
+            time_mgr.set_fixed_dt(Some(0.25f32));
+            time_mgr.update();
+
+            Self {
+                world,
+                time_mgr,
+                transform_mgr,
+                ui_mgr: UIManager::new(),
+                entity,
+                transform,
+            }
+        }
+
+        fn step(&mut self) -> Vec<(Entity, AnimationNotification)> {
+            animate_state_machines(
+                &mut self.world,
+                &self.time_mgr,
+                &mut self.transform_mgr,
+                &mut self.ui_mgr,
+            )
+        }
+
+        fn x(&self) -> f32 {
+            self.transform_mgr.transform(self.transform).position.x
+        }
+
+        fn animator(&mut self) -> &mut StateMachineAnimator {
+            self.world
+                .entry(self.entity)
+                .unwrap()
+                .into_component_mut::<StateMachineAnimator>()
+                .unwrap()
+        }
+    }
+
+    fn animator(duration: f32) -> StateMachineAnimator {
+        StateMachineAnimator::new(
+            HashMap::from([
+                ("idle".to_owned(), state(0f64)),
+                ("run".to_owned(), state(10f64)),
+            ]),
+            vec![AnimatorTransition {
+                from: Some("idle".to_owned()),
+                to: "run".to_owned(),
+                conditions: vec![AnimatorCondition {
+                    parameter: "go".to_owned(),
+                    comparison: None,
+                }],
+                duration,
+                exit_time: None,
+            }],
+            HashMap::from([("go".to_owned(), AnimatorParameter::Trigger(false))]),
+            "idle",
+        )
+    }
+
+    #[test]
+    fn transitions_are_taken_once_their_conditions_are_met() {
+        let mut fixture = Fixture::new(animator(0f32));
+
+        assert!(fixture.step().is_empty());
+        assert_eq!(fixture.animator().state(), "idle");
+        assert_eq!(fixture.x(), 0f32);
+
+        fixture
+            .animator()
+            .set_parameter("go", AnimatorParameter::Trigger(true))
+            .unwrap();
+        let notifications = fixture.step();
+
+        assert!(matches!(
+            notifications.as_slice(),
+            [(_, AnimationNotification::StateChange { from, to })] if from == "idle" && to == "run"
+        ));
+        assert_eq!(fixture.animator().state(), "run");
+        assert!(!fixture.animator().is_transitioning());
+        assert_eq!(
+            fixture.animator().parameter("go"),
+            Some(AnimatorParameter::Trigger(false))
+        );
+        assert_eq!(fixture.x(), 10f32);
+    }
+
+    #[test]
+    fn crossfades_blend_from_the_previous_state() {
+        let mut fixture = Fixture::new(animator(1f32));
+
+        fixture
+            .animator()
+            .set_parameter("go", AnimatorParameter::Trigger(true))
+            .unwrap();
+
+        for expected in [2.5f32, 5f32, 7.5f32] {
+            fixture.step();
+            assert!(fixture.animator().is_transitioning());
+            assert_eq!(fixture.x(), expected);
+        }
+
+        fixture.step();
+        assert!(!fixture.animator().is_transitioning());
+        assert_eq!(fixture.x(), 10f32);
+    }
+}
@@ -0,0 +1,64 @@
+use crate::animation::{Tween, TweenId, TweenManager};
+use crate::system::apply_time_line;
+use crate::time::TimeManager;
+use crate::transform::TransformManager;
+use crate::ui::UIManager;
+use legion::*;
+
+// Returns the completed tweens in the order they were created, and the cancelled ones.
+pub fn animate_tweens(
+    world: &mut World,
+    time_mgr: &TimeManager,
+    transform_mgr: &mut TransformManager,
+    ui_mgr: &mut UIManager,
+    tween_mgr: &mut TweenManager,
+) -> (Vec<(TweenId, Tween)>, Vec<Tween>) {
+    let mut cancelled = Vec::new();
+    let mut finished = Vec::new();
+
+    for (id, tween) in tween_mgr.iter_mut() {
+        if tween.is_pending {
+            continue;
+        }
+
+        // The entity has been destroyed.
+        if !world.contains(tween.entity) {
+            cancelled.push(id);
+            continue;
+        }
+
+        let step = if let Some(step) = tween.advance(time_mgr.dt()) {
+            step
+        } else {
+            continue;
+        };
+        let key_frame = &tween.time_line.key_frames[0];
+        let application = apply_time_line(
+            world,
+            transform_mgr,
+            ui_mgr,
+            tween.entity,
+            &tween.time_line,
+            key_frame,
+            key_frame.easing.ease(step.normalized_time),
+        );
+
+        if application.is_invalid() {
+            application.report(&tween.time_line);
+            cancelled.push(id);
+        } else if step.finished {
+            finished.push(id);
+        }
+    }
+
+    let cancelled = cancelled
+        .into_iter()
+        .flat_map(|id| tween_mgr.cancel(id))
+        .collect();
+    let completed = finished
+        .into_iter()
+        .filter_map(|id| tween_mgr.complete(id).map(|tween| (id, tween)))
+        .collect();
+
+    (completed, cancelled)
+}
@@ -0,0 +1,36 @@
+use crate::component::{Transform, UIElement};
+use crate::event::EntityEventManager;
+use crate::transform::TransformManager;
+use crate::ui::{UIEventManager, UIManager};
+use legion::*;
+
+pub fn destroy_entities(
+    world: &mut World,
+    transform_mgr: &mut TransformManager,
+    ui_mgr: &mut UIManager,
+    entity_event_mgr: &mut EntityEventManager,
+    ui_event_mgr: &mut UIEventManager,
+    entities: Vec<Entity>,
+) {
+    for entity in entities {
+        let (transform, ui_element) = match world.entry(entity) {
+            Some(entry) => (
+                entry.get_component::<Transform>().ok().map(|t| t.index()),
+                entry.get_component::<UIElement>().ok().map(|e| e.index()),
+            ),
+            None => continue,
+        };
+
+        if let Some(transform) = transform {
+            transform_mgr.dealloc(transform);
+        }
+
+        if let Some(ui_element) = ui_element {
+            ui_mgr.dealloc(ui_element);
+        }
+
+        entity_event_mgr.remove_entity(entity);
+        ui_event_mgr.remove_entity(entity);
+        world.remove(entity);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::event::events;
+use crate::input::{GamepadBackend, GamepadEvent};
+use crate::system::System;
+use crate::EngineContextWithoutSystemManager;
+
+// Feeds the gamepad events to the input manager and notifies them to Lua.
+pub struct GamepadSystem {
+    backend: Box<dyn GamepadBackend>,
+    events: Vec<GamepadEvent>,
+}
+
+impl GamepadSystem {
+    pub fn new(backend: Box<dyn GamepadBackend>) -> Self {
+        Self {
+            backend,
+            events: Vec::new(),
+        }
+    }
+}
+
+impl System for GamepadSystem {
+    fn run(&mut self, context: &EngineContextWithoutSystemManager) {
+        self.backend.poll(&mut self.events);
+
+        for event in self.events.drain(..) {
+            context.input_mgr_mut().handle_gamepad_event(&event);
+
+            let dispatcher = context.event_mgr().dispatcher();
+            let lua = context.lua_mgr().lua();
+
+            match event {
+                GamepadEvent::Connected { id, name } => {
+                    dispatcher.emit(lua, &events::GamepadConnected { gamepad: id, name })
+                }
+                GamepadEvent::Disconnected { id } => {
+                    dispatcher.emit(lua, &events::GamepadDisconnected { gamepad: id })
+                }
+                GamepadEvent::Button {
+                    id,
+                    button,
+                    is_pressed: true,
+                } => dispatcher.emit(
+                    lua,
+                    &events::GamepadButtonDown {
+                        gamepad: id,
+                        button: button.name(),
+                    },
+                ),
+                GamepadEvent::Button {
+                    id,
+                    button,
+                    is_pressed: false,
+                } => dispatcher.emit(
+                    lua,
+                    &events::GamepadButtonUp {
+                        gamepad: id,
+                        button: button.name(),
+                    },
+                ),
+                // Axes are polled through the input manager instead.
+                GamepadEvent::Axis { .. } => {}
+            }
+        }
+    }
+}
@@ -1,9 +1,21 @@
+mod animate_flipbooks;
 mod animate_single_animators;
+mod animate_state_machines;
+mod animate_tweens;
+mod destroy_entities;
+mod gamepad_system;
 mod renderer_system;
+mod replace_reloaded_assets;
 mod system;
 mod system_manager;
 
+pub use animate_flipbooks::*;
 pub use animate_single_animators::*;
+pub use animate_state_machines::*;
+pub use animate_tweens::*;
+pub use destroy_entities::*;
+pub use gamepad_system::*;
 pub use renderer_system::*;
+pub use replace_reloaded_assets::*;
 pub use system::*;
 pub use system_manager::*;
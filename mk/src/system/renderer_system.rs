@@ -11,13 +11,125 @@ use fontdue::layout::VerticalAlign;
 use legion::*;
 use std::cmp::{max, min};
 use std::mem::size_of;
+use std::ops::Range;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum InstanceKind {
+    Sprite,
+    Glyph,
+}
+
+impl InstanceKind {
+    // Number of floats per instance.
+    fn stride(self) -> usize {
+        match self {
+            Self::Sprite => 19,
+            Self::Glyph => 21,
+        }
+    }
+
+    // Per-instance attributes and their offsets in floats.
+    fn attributes(self) -> &'static [(&'static str, usize)] {
+        match self {
+            Self::Sprite => &[
+                ("transform", 0),
+                ("size", 9),
+                ("color", 11),
+                ("uv_rect", 15),
+            ],
+            Self::Glyph => &[
+                ("transform", 0),
+                ("size", 9),
+                ("color", 11),
+                ("thickness", 15),
+                ("smoothness", 16),
+                ("uv_rect", 17),
+            ],
+        }
+    }
+
+    fn texture_uniform(self) -> &'static str {
+        match self {
+            Self::Sprite => "sprite",
+            Self::Glyph => "glyph",
+        }
+    }
+}
+
+// An axis-aligned rectangle in world space, used to skip renderers that are out of the camera.
+#[derive(Debug, Clone, Copy)]
+struct Bounds {
+    min: Vec2,
+    max: Vec2,
+}
+
+impl Bounds {
+    // Bounds of the local rectangle that spans to the right and downwards from the given position.
+    fn from_local_rect(matrix: &[f32; 9], x: f32, y: f32, width: f32, height: f32) -> Self {
+        let corners = [
+            (x, y),
+            (x + width, y),
+            (x, y - height),
+            (x + width, y - height),
+        ];
+        let mut min = Vec2::new(f32::INFINITY, f32::INFINITY);
+        let mut max = Vec2::new(f32::NEG_INFINITY, f32::NEG_INFINITY);
+
+        for (x, y) in corners {
+            let world_x = matrix[0] * x + matrix[3] * y + matrix[6];
+            let world_y = matrix[1] * x + matrix[4] * y + matrix[7];
+            min.x = min.x.min(world_x);
+            min.y = min.y.min(world_y);
+            max.x = max.x.max(world_x);
+            max.y = max.y.max(world_y);
+        }
+
+        Self { min, max }
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+}
+
+// Instances that share the shader and the texture, drawn with a single instanced draw call.
+#[derive(Debug, Clone)]
+struct Batch {
+    order: isize,
+    kind: InstanceKind,
+    shader: Arc<Shader>,
+    texture: NativeHandle,
+    instances: Range<usize>,
+}
+
+impl Batch {
+    fn sort_key(&self) -> (isize, InstanceKind, NativeHandle, NativeHandle) {
+        (self.order, self.kind, self.shader.handle(), self.texture)
+    }
+
+    fn try_merge(&mut self, other: &Batch) -> bool {
+        if self.kind != other.kind
+            || self.shader.handle() != other.shader.handle()
+            || self.texture != other.texture
+            || self.instances.end != other.instances.start
+        {
+            return false;
+        }
+
+        self.instances.end = other.instances.end;
+        true
+    }
+}
 
 pub struct RendererSystem {
     renderer_bump: Bump,
     extra_bump: Bump,
     glyph_buffer: Buffer,
     sprite_buffer: Buffer,
-    tilemap_sprite_buffer: Buffer,
 }
 
 impl RendererSystem {
@@ -64,34 +176,12 @@ impl RendererSystem {
             0f32, 0f32, //
             0f32, 1f32, //
         ]);
-        let tilemap_sprite_buffer = Buffer::from_slice(&[
-            1f32, 1f32, //
-            1f32, 0f32, //
-            //
-            0f32, 0f32, //
-            0f32, 1f32, //
-            //
-            1f32, 0f32, //
-            1f32, 1f32, //
-            //
-            //
-            //
-            1f32, 1f32, //
-            1f32, 0f32, //
-            //
-            0f32, 1f32, //
-            0f32, 0f32, //
-            //
-            0f32, 0f32, //
-            0f32, 1f32, //
-        ]);
 
         Self {
             renderer_bump: Bump::with_capacity(4 * 1024),
             extra_bump: Bump::with_capacity(1 * 1024),
             glyph_buffer,
             sprite_buffer,
-            tilemap_sprite_buffer,
         }
     }
 }
@@ -107,17 +197,74 @@ impl System for RendererSystem {
         let (world, mut rest_world) = world.split_for_query(&camera_query);
 
         let mut cameras = BumpVec::from_iter_in(camera_query.iter(&world), &self.extra_bump);
-        cameras.sort_unstable_by(|lhs, rhs| lhs.1.order.cmp(&rhs.1.order));
+        // Cameras with targets go first so that their textures are up to date for the screen.
+        cameras.sort_unstable_by_key(|(_, camera)| (camera.target.is_none(), camera.order));
 
         let mut render_mgr = context.render_mgr_mut();
         let screen_mgr = context.screen_mgr();
         let transform_mgr = context.transform_mgr();
         let mut glyph_mgr = context.glyph_mgr_mut();
 
-        let width_half = (screen_mgr.width() * 0.5) as f32;
-        let height_half = (screen_mgr.height() * 0.5) as f32;
+        let screen_width = screen_mgr.width() as f32;
+        let screen_height = screen_mgr.height() as f32;
+        let physical_width = screen_mgr.physical_width() as f32;
+        let physical_height = screen_mgr.physical_height() as f32;
 
         for (camera_transform, camera) in cameras {
+            let (surface_width, surface_height, physical_surface_width, physical_surface_height) =
+                match &camera.target {
+                    Some(target) => {
+                        let (width, height) = (target.width() as f32, target.height() as f32);
+                        target.bind();
+                        (width, height, width, height)
+                    }
+                    None => {
+                        RenderTarget::bind_default();
+                        (screen_width, screen_height, physical_width, physical_height)
+                    }
+                };
+
+            // Targets are rendered upside down, so that their textures have the top row first as
+            // the loaded ones do.
+            let is_flipped = camera.target.is_some();
+            let viewport_bottom = if is_flipped {
+                1f32 - camera.viewport.y - camera.viewport.height
+            } else {
+                camera.viewport.y
+            };
+
+            // Edges are rounded separately so that adjacent viewports neither overlap nor leave gaps.
+            let viewport_x = (camera.viewport.x * physical_surface_width).round() as i32;
+            let viewport_y = (viewport_bottom * physical_surface_height).round() as i32;
+            let viewport_width = ((camera.viewport.x + camera.viewport.width)
+                * physical_surface_width)
+                .round() as i32
+                - viewport_x;
+            let viewport_height = ((viewport_bottom + camera.viewport.height)
+                * physical_surface_height)
+                .round() as i32
+                - viewport_y;
+
+            if viewport_width <= 0 || viewport_height <= 0 {
+                continue;
+            }
+
+            set_viewport(
+                viewport_x,
+                viewport_y,
+                viewport_width as u32,
+                viewport_height as u32,
+            );
+
+            if let Some(color) = camera.clear_color {
+                clear_color(color.r, color.g, color.b, color.a);
+            }
+
+            if camera.clear_depth {
+                clear_depth();
+            }
+
+            let (width_half, height_half) = camera.half_extents(surface_width, surface_height);
             let camera_transform_index = camera_transform.index();
             let camera_transform = transform_mgr.transform(camera_transform_index);
             let mut ndc_to_world = transform_mgr
@@ -129,6 +276,7 @@ impl System for RendererSystem {
             ndc_to_world[3] *= height_half;
             ndc_to_world[4] *= height_half;
 
+            let camera_bounds = Bounds::from_local_rect(&ndc_to_world, -1f32, 1f32, 2f32, 2f32);
             let mut camera_matrix_inverse = [0f32; 9];
 
             camera_transform.to_matrix_inverse_with_scale(
@@ -137,8 +285,15 @@ impl System for RendererSystem {
                 &mut camera_matrix_inverse,
             );
 
-            let mut buffers = bump_vec![in &self.extra_bump];
-            let mut renderers = bump_vec![in &self.extra_bump];
+            if is_flipped {
+                camera_matrix_inverse[1] = -camera_matrix_inverse[1];
+                camera_matrix_inverse[4] = -camera_matrix_inverse[4];
+                camera_matrix_inverse[7] = -camera_matrix_inverse[7];
+            }
+
+            let mut sprite_instances = bump_vec![in &self.extra_bump];
+            let mut glyph_instances = bump_vec![in &self.extra_bump];
+            let mut batches: BumpVec<Batch> = bump_vec![in &self.extra_bump];
             let sdf_inset = glyph_mgr.sdf_inset();
 
             <(&Transform, &Size, &mut GlyphRenderer)>::query()
@@ -151,6 +306,8 @@ impl System for RendererSystem {
                     let color = renderer.color;
                     let thickness = renderer.thickness;
                     let smoothness = renderer.smoothness;
+                    let order = renderer.order;
+                    let shader = renderer.shader.clone();
                     let layout_size = renderer.compute_size();
                     let (horizontal_align, vertical_align) = (
                         match renderer.config().horizontal_align {
@@ -172,13 +329,12 @@ impl System for RendererSystem {
                     );
                     let offset = alignment_offset - overflow_offset;
                     let matrix = transform_mgr.transform_world_matrix(transform.index());
-                    let mut texture_and_buffers = bump_vec![in &self.extra_bump];
+                    let first_batch = batches.len();
 
                     let (font, layout) = renderer.font_and_layout();
 
                     for glyph in layout.glyphs() {
                         let g = glyph_mgr.glyph(font, glyph.key);
-                        let mut buffer = render_mgr.alloc_buffer();
 
                         let font_width_scale = glyph.width as f32
                             / (g.mapping.width() as usize - 2 * sdf_inset) as f32;
@@ -186,24 +342,30 @@ impl System for RendererSystem {
                             / (g.mapping.height() as usize - 2 * sdf_inset) as f32;
                         let glyph_width = g.mapping.width() as f32 * font_width_scale;
                         let glyph_height = g.mapping.height() as f32 * font_height_scale;
+                        let glyph_x = glyph.x + offset.x - sdf_inset as f32 * font_width_scale;
+                        let glyph_y = glyph.y - offset.y - sdf_inset as f32 * font_height_scale;
 
-                        buffer.replace(&[
+                        if !camera_bounds.overlaps(&Bounds::from_local_rect(
+                            matrix,
+                            glyph_x,
+                            glyph_y,
+                            glyph_width,
+                            glyph_height,
+                        )) {
+                            continue;
+                        }
+
+                        let instance = glyph_instances.len() / InstanceKind::Glyph.stride();
+
+                        glyph_instances.extend_from_slice(&[
                             matrix[0],
                             matrix[1],
                             matrix[2],
                             matrix[3],
                             matrix[4],
                             matrix[5],
-                            matrix[6]
-                                + matrix[0]
-                                    * (glyph.x + offset.x - sdf_inset as f32 * font_width_scale)
-                                + matrix[3]
-                                    * (glyph.y - offset.y - sdf_inset as f32 * font_height_scale),
-                            matrix[7]
-                                + matrix[1]
-                                    * (glyph.x + offset.x - sdf_inset as f32 * font_width_scale)
-                                + matrix[4]
-                                    * (glyph.y - offset.y - sdf_inset as f32 * font_height_scale),
+                            matrix[6] + matrix[0] * glyph_x + matrix[3] * glyph_y,
+                            matrix[7] + matrix[1] * glyph_x + matrix[4] * glyph_y,
                             matrix[8],
                             glyph_width,
                             glyph_height,
@@ -219,97 +381,22 @@ impl System for RendererSystem {
                             (g.mapping.max().1 as f32 - 0.5f32) / g.texture.height() as f32,
                         ]);
 
-                        texture_and_buffers.push((g.texture.handle(), buffer));
-                    }
-
-                    let shader = &renderer.shader;
-                    let mut r = Renderer::new(&self.renderer_bump);
-
-                    // TODO: Merge instances that have the same texture to reduce draw calls.
-
-                    for (texture, buffer) in texture_and_buffers {
-                        r.enqueue(1, 2, RenderMode::Trangles, shader, |req| {
-                            render_mgr.apply_common_shader_input(shader, req);
-
-                            // TODO: Add shader type checking logic to alert if types have no match.
-
-                            if let Some(uniform) = shader.uniform("camera") {
-                                req.uniform_f33(uniform.location, camera_matrix_inverse);
-                            }
-                            if let Some(uniform) = shader.uniform("glyph") {
-                                req.uniform_texture_raw(uniform.location, texture);
-                            }
-
-                            if let Some(attribute) = shader.attribute("pos") {
-                                req.attribute(
-                                    attribute.location,
-                                    &self.glyph_buffer,
-                                    0,
-                                    attribute.ty,
-                                );
-                            }
-                            if let Some(attribute) = shader.attribute("uv") {
-                                req.attribute(
-                                    attribute.location,
-                                    &self.glyph_buffer,
-                                    (size_of::<f32>() * 2) as _,
-                                    attribute.ty,
-                                );
-                            }
+                        let batch = Batch {
+                            order,
+                            kind: InstanceKind::Glyph,
+                            shader: shader.clone(),
+                            texture: g.texture.handle(),
+                            instances: instance..instance + 1,
+                        };
 
-                            if let Some(attribute) = shader.attribute("transform") {
-                                req.attribute_per_instance(
-                                    attribute.location,
-                                    &buffer,
-                                    0,
-                                    attribute.ty,
-                                );
-                            }
-                            if let Some(attribute) = shader.attribute("size") {
-                                req.attribute_per_instance(
-                                    attribute.location,
-                                    &buffer,
-                                    (size_of::<f32>() * 9) as _,
-                                    attribute.ty,
-                                );
-                            }
-                            if let Some(attribute) = shader.attribute("color") {
-                                req.attribute_per_instance(
-                                    attribute.location,
-                                    &buffer,
-                                    (size_of::<f32>() * 11) as _,
-                                    attribute.ty,
-                                );
-                            }
-                            if let Some(attribute) = shader.attribute("thickness") {
-                                req.attribute_per_instance(
-                                    attribute.location,
-                                    &buffer,
-                                    (size_of::<f32>() * 15) as _,
-                                    attribute.ty,
-                                );
-                            }
-                            if let Some(attribute) = shader.attribute("smoothness") {
-                                req.attribute_per_instance(
-                                    attribute.location,
-                                    &buffer,
-                                    (size_of::<f32>() * 16) as _,
-                                    attribute.ty,
-                                );
-                            }
-                            if let Some(attribute) = shader.attribute("uv_rect") {
-                                req.attribute_per_instance(
-                                    attribute.location,
-                                    &buffer,
-                                    (size_of::<f32>() * 17) as _,
-                                    attribute.ty,
-                                );
-                            }
-                        });
-                        buffers.push(buffer);
+                        // Glyphs in the same text mostly share a texture.
+                        if !batches[first_batch..]
+                            .last_mut()
+                            .is_some_and(|last| last.try_merge(&batch))
+                        {
+                            batches.push(batch);
+                        }
                     }
-
-                    renderers.push((renderer.order, r));
                 });
             <(&Transform, &Size, &mut SpriteRenderer)>::query()
                 .filter(!component::<Diagnostic>())
@@ -319,10 +406,21 @@ impl System for RendererSystem {
                     }
 
                     let matrix = transform_mgr.transform_world_matrix(transform.index());
+
+                    if !camera_bounds.overlaps(&Bounds::from_local_rect(
+                        matrix,
+                        -size.width * 0.5f32,
+                        size.height * 0.5f32,
+                        size.width,
+                        size.height,
+                    )) {
+                        return;
+                    }
+
                     let sprite = &renderer.sprite;
-                    let mut buffer = render_mgr.alloc_buffer();
+                    let instance = sprite_instances.len() / InstanceKind::Sprite.stride();
 
-                    buffer.replace(&[
+                    sprite_instances.extend_from_slice(&[
                         matrix[0],
                         matrix[1],
                         matrix[2],
@@ -348,68 +446,13 @@ impl System for RendererSystem {
                         (sprite.texel_mapping().max().1 as f32) / sprite.texture().height() as f32,
                     ]);
 
-                    let shader = &renderer.shader;
-                    let mut r = Renderer::new(&self.renderer_bump);
-
-                    r.enqueue(1, 2, RenderMode::Trangles, shader, |req| {
-                        render_mgr.apply_common_shader_input(shader, req);
-
-                        // TODO: Add shader type checking logic to alert if types have no match.
-
-                        if let Some(uniform) = shader.uniform("camera") {
-                            req.uniform_f33(uniform.location, camera_matrix_inverse);
-                        }
-                        if let Some(uniform) = shader.uniform("sprite") {
-                            req.uniform_texture(uniform.location, sprite.texture());
-                        }
-
-                        if let Some(attribute) = shader.attribute("pos") {
-                            req.attribute(attribute.location, &self.sprite_buffer, 0, attribute.ty);
-                        }
-                        if let Some(attribute) = shader.attribute("uv") {
-                            req.attribute(
-                                attribute.location,
-                                &self.sprite_buffer,
-                                (size_of::<f32>() * 2) as _,
-                                attribute.ty,
-                            );
-                        }
-
-                        if let Some(attribute) = shader.attribute("transform") {
-                            req.attribute_per_instance(
-                                attribute.location,
-                                &buffer,
-                                0,
-                                attribute.ty,
-                            );
-                        }
-                        if let Some(attribute) = shader.attribute("size") {
-                            req.attribute_per_instance(
-                                attribute.location,
-                                &buffer,
-                                (size_of::<f32>() * 9) as _,
-                                attribute.ty,
-                            );
-                        }
-                        if let Some(attribute) = shader.attribute("color") {
-                            req.attribute_per_instance(
-                                attribute.location,
-                                &buffer,
-                                (size_of::<f32>() * 11) as _,
-                                attribute.ty,
-                            );
-                        }
-                        if let Some(attribute) = shader.attribute("uv_rect") {
-                            req.attribute_per_instance(
-                                attribute.location,
-                                &buffer,
-                                (size_of::<f32>() * 15) as _,
-                                attribute.ty,
-                            );
-                        }
+                    batches.push(Batch {
+                        order: renderer.order,
+                        kind: InstanceKind::Sprite,
+                        shader: renderer.shader.clone(),
+                        texture: sprite.texture().handle(),
+                        instances: instance..instance + 1,
                     });
-                    buffers.push(buffer);
-                    renderers.push((renderer.order, r));
                 });
             <(&Transform, &Size, &mut NinePatchRenderer)>::query()
                 .filter(!component::<Diagnostic>())
@@ -419,6 +462,17 @@ impl System for RendererSystem {
                     }
 
                     let matrix = transform_mgr.transform_world_matrix(transform.index());
+
+                    if !camera_bounds.overlaps(&Bounds::from_local_rect(
+                        matrix,
+                        -size.width * 0.5f32,
+                        size.height * 0.5f32,
+                        size.width,
+                        size.height,
+                    )) {
+                        return;
+                    }
+
                     let nine_patch = &renderer.nine_patch;
 
                     let left = nine_patch.sprite_lt().width() as f32;
@@ -441,10 +495,10 @@ impl System for RendererSystem {
                         (top * ratio, bottom * ratio)
                     };
 
-                    let mut buffer_data: Vec<f32> = Vec::with_capacity(9 * 19);
+                    let instance = sprite_instances.len() / InstanceKind::Sprite.stride();
                     let mut enqueue_patch =
                         |offset_x: f32, offset_y: f32, width: f32, height: f32, sprite: &Sprite| {
-                            buffer_data.extend(&[
+                            sprite_instances.extend_from_slice(&[
                                 matrix[0],
                                 matrix[1],
                                 matrix[2],
@@ -552,71 +606,13 @@ impl System for RendererSystem {
                         );
                     }
 
-                    let mut buffer = render_mgr.alloc_buffer();
-                    buffer.replace(buffer_data.as_slice());
-
-                    let shader = &renderer.shader;
-                    let mut r = Renderer::new(&self.renderer_bump);
-
-                    r.enqueue(patch_count, 2, RenderMode::Trangles, shader, |req| {
-                        render_mgr.apply_common_shader_input(shader, req);
-
-                        // TODO: Add shader type checking logic to alert if types have no match.
-
-                        if let Some(uniform) = shader.uniform("camera") {
-                            req.uniform_f33(uniform.location, camera_matrix_inverse);
-                        }
-                        if let Some(uniform) = shader.uniform("sprite") {
-                            req.uniform_texture(uniform.location, nine_patch.texture());
-                        }
-
-                        if let Some(attribute) = shader.attribute("pos") {
-                            req.attribute(attribute.location, &self.sprite_buffer, 0, attribute.ty);
-                        }
-                        if let Some(attribute) = shader.attribute("uv") {
-                            req.attribute(
-                                attribute.location,
-                                &self.sprite_buffer,
-                                (size_of::<f32>() * 2) as _,
-                                attribute.ty,
-                            );
-                        }
-
-                        if let Some(attribute) = shader.attribute("transform") {
-                            req.attribute_per_instance(
-                                attribute.location,
-                                &buffer,
-                                0,
-                                attribute.ty,
-                            );
-                        }
-                        if let Some(attribute) = shader.attribute("size") {
-                            req.attribute_per_instance(
-                                attribute.location,
-                                &buffer,
-                                (size_of::<f32>() * 9) as _,
-                                attribute.ty,
-                            );
-                        }
-                        if let Some(attribute) = shader.attribute("color") {
-                            req.attribute_per_instance(
-                                attribute.location,
-                                &buffer,
-                                (size_of::<f32>() * 11) as _,
-                                attribute.ty,
-                            );
-                        }
-                        if let Some(attribute) = shader.attribute("uv_rect") {
-                            req.attribute_per_instance(
-                                attribute.location,
-                                &buffer,
-                                (size_of::<f32>() * 15) as _,
-                                attribute.ty,
-                            );
-                        }
+                    batches.push(Batch {
+                        order: renderer.order,
+                        kind: InstanceKind::Sprite,
+                        shader: renderer.shader.clone(),
+                        texture: nine_patch.texture().handle(),
+                        instances: instance..instance + patch_count,
                     });
-                    buffers.push(buffer);
-                    renderers.push((renderer.order, r));
                 });
             <(&Transform, &mut TilemapRenderer)>::query()
                 .filter(!component::<Diagnostic>())
@@ -704,10 +700,8 @@ impl System for RendererSystem {
                     );
 
                     let sprites = renderer.tilemap.palette.sprites();
+                    let instance = sprite_instances.len() / InstanceKind::Sprite.stride();
                     let mut instance_count = 0;
-                    let mut per_instance_buffer = Vec::with_capacity(
-                        renderer.tilemap.tile_count_x * renderer.tilemap.tile_count_y * 20,
-                    );
 
                     for layer in &renderer.tilemap.layers {
                         for y in range_min_y..range_max_y {
@@ -723,7 +717,7 @@ impl System for RendererSystem {
                                 let offset_y = y as f32 * tile_height;
 
                                 instance_count += 1;
-                                per_instance_buffer.extend([
+                                sprite_instances.extend_from_slice(&[
                                     matrix[0],
                                     matrix[1],
                                     matrix[2],
@@ -752,14 +746,80 @@ impl System for RendererSystem {
                         }
                     }
 
-                    let shader = &renderer.shader;
-                    let mut buffer = render_mgr.alloc_buffer();
+                    batches.push(Batch {
+                        order: renderer.order,
+                        kind: InstanceKind::Sprite,
+                        shader: renderer.shader.clone(),
+                        texture: renderer.tilemap.palette.texture().handle(),
+                        instances: instance..instance + instance_count,
+                    });
+                });
+
+            // A texture can't be sampled while it's being rendered into, so renderers showing the
+            // camera's own target are left out.
+            if let Some(target) = &camera.target {
+                let target_texture = target.texture().handle();
+                batches.retain(|batch| batch.texture != target_texture);
+            }
+
+            // The order between renderers of the same order is unspecified, so they're grouped by
+            // their shader and texture to make the batches as long as possible.
+            batches.sort_by_key(|batch| batch.sort_key());
+
+            // Instances are repacked in the drawing order so that consecutive batches can be merged.
+            let mut packed_sprite_instances =
+                BumpVec::with_capacity_in(sprite_instances.len(), &self.extra_bump);
+            let mut packed_glyph_instances =
+                BumpVec::with_capacity_in(glyph_instances.len(), &self.extra_bump);
+            let mut merged_batches: BumpVec<Batch> = bump_vec![in &self.extra_bump];
+
+            for batch in batches {
+                let (instances, packed_instances) = match batch.kind {
+                    InstanceKind::Sprite => (&sprite_instances, &mut packed_sprite_instances),
+                    InstanceKind::Glyph => (&glyph_instances, &mut packed_glyph_instances),
+                };
+                let stride = batch.kind.stride();
+                let instance = packed_instances.len() / stride;
+
+                packed_instances.extend_from_slice(
+                    &instances[batch.instances.start * stride..batch.instances.end * stride],
+                );
+
+                let batch = Batch {
+                    instances: instance..instance + batch.instances.len(),
+                    ..batch
+                };
+
+                if !merged_batches
+                    .last_mut()
+                    .is_some_and(|last| last.try_merge(&batch))
+                {
+                    merged_batches.push(batch);
+                }
+            }
 
-                    buffer.replace(per_instance_buffer.as_slice());
+            let mut sprite_instance_buffer = render_mgr.alloc_buffer();
+            let mut glyph_instance_buffer = render_mgr.alloc_buffer();
 
-                    let mut r = Renderer::new(&self.renderer_bump);
+            sprite_instance_buffer.replace(packed_sprite_instances.as_slice());
+            glyph_instance_buffer.replace(packed_glyph_instances.as_slice());
 
-                    r.enqueue(instance_count, 2, RenderMode::Trangles, shader, |req| {
+            let mut r = Renderer::new(&self.renderer_bump);
+
+            for batch in &merged_batches {
+                let shader = &batch.shader;
+                let (buffer, instance_buffer) = match batch.kind {
+                    InstanceKind::Sprite => (&self.sprite_buffer, &sprite_instance_buffer),
+                    InstanceKind::Glyph => (&self.glyph_buffer, &glyph_instance_buffer),
+                };
+                let base_offset = size_of::<f32>() * batch.kind.stride() * batch.instances.start;
+
+                r.enqueue(
+                    batch.instances.len() as u32,
+                    2,
+                    RenderMode::Trangles,
+                    shader,
+                    |req| {
                         render_mgr.apply_common_shader_input(shader, req);
 
                         // TODO: Add shader type checking logic to alert if types have no match.
@@ -767,76 +827,74 @@ impl System for RendererSystem {
                         if let Some(uniform) = shader.uniform("camera") {
                             req.uniform_f33(uniform.location, camera_matrix_inverse);
                         }
-                        if let Some(uniform) = shader.uniform("sprite") {
-                            req.uniform_texture(
-                                uniform.location,
-                                renderer.tilemap.palette.texture(),
-                            );
+                        if let Some(uniform) = shader.uniform(batch.kind.texture_uniform()) {
+                            req.uniform_texture_raw(uniform.location, batch.texture);
                         }
 
                         if let Some(attribute) = shader.attribute("pos") {
-                            req.attribute(
-                                attribute.location,
-                                &self.tilemap_sprite_buffer,
-                                0,
-                                attribute.ty,
-                            );
+                            req.attribute(attribute.location, buffer, 0, attribute.ty);
                         }
                         if let Some(attribute) = shader.attribute("uv") {
                             req.attribute(
                                 attribute.location,
-                                &self.tilemap_sprite_buffer,
+                                buffer,
                                 (size_of::<f32>() * 2) as _,
                                 attribute.ty,
                             );
                         }
 
-                        if let Some(attribute) = shader.attribute("transform") {
-                            req.attribute_per_instance(
-                                attribute.location,
-                                &buffer,
-                                0,
-                                attribute.ty,
-                            );
-                        }
-                        if let Some(attribute) = shader.attribute("size") {
-                            req.attribute_per_instance(
-                                attribute.location,
-                                &buffer,
-                                (size_of::<f32>() * 9) as _,
-                                attribute.ty,
-                            );
-                        }
-                        if let Some(attribute) = shader.attribute("color") {
-                            req.attribute_per_instance(
-                                attribute.location,
-                                &buffer,
-                                (size_of::<f32>() * 11) as _,
-                                attribute.ty,
-                            );
-                        }
-                        if let Some(attribute) = shader.attribute("uv_rect") {
-                            req.attribute_per_instance(
-                                attribute.location,
-                                &buffer,
-                                (size_of::<f32>() * 15) as _,
-                                attribute.ty,
-                            );
+                        for &(name, offset) in batch.kind.attributes() {
+                            if let Some(attribute) = shader.attribute(name) {
+                                req.attribute_per_instance(
+                                    attribute.location,
+                                    instance_buffer,
+                                    (base_offset + size_of::<f32>() * offset) as _,
+                                    attribute.ty,
+                                );
+                            }
                         }
-                    });
-                    buffers.push(buffer);
-                    renderers.push((renderer.order, r));
-                });
-
-            renderers.sort_unstable_by_key(|(order, _)| *order);
-
-            for (_, renderer) in renderers {
-                renderer.flush();
+                    },
+                );
             }
 
-            for buffer in buffers {
-                render_mgr.dealloc_buffer(buffer);
-            }
+            r.flush();
+
+            render_mgr.dealloc_buffer(sprite_instance_buffer);
+            render_mgr.dealloc_buffer(glyph_instance_buffer);
         }
+
+        RenderTarget::bind_default();
+        reset_viewport(physical_width as u32, physical_height as u32);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_follow_rotated_sprites() {
+        // A 10x10 camera at the origin, and a 2x2 sprite at (5, 0) rotated by 45 degrees.
+        let camera = Bounds::from_local_rect(
+            &[5f32, 0f32, 0f32, 0f32, 5f32, 0f32, 0f32, 0f32, 1f32],
+            -1f32,
+            1f32,
+            2f32,
+            2f32,
+        );
+        let (sin, cos) = std::f32::consts::FRAC_PI_4.sin_cos();
+        let sprite = |x: f32| {
+            Bounds::from_local_rect(
+                &[cos, sin, 0f32, -sin, cos, 0f32, x, 0f32, 1f32],
+                -1f32,
+                1f32,
+                2f32,
+                2f32,
+            )
+        };
+
+        // The rotated corner reaches about 1.41 units out.
+        assert!(camera.overlaps(&sprite(6.4f32)));
+        assert!(!camera.overlaps(&sprite(6.5f32)));
     }
 }
@@ -0,0 +1,92 @@
+use crate::asset::AssetReload;
+use crate::component::*;
+use legion::*;
+
+pub fn replace_reloaded_assets(world: &mut World, reloads: &[AssetReload]) {
+    for reload in reloads {
+        if reload.is_failed() {
+            continue;
+        }
+
+        for flipbook in <&mut FlipbookAnimator>::query().iter_mut(world) {
+            match &mut flipbook.source {
+                FlipbookSource::Grid(grid) => {
+                    if let Some(replacement) = reload.replacement(grid) {
+                        *grid = replacement;
+                    }
+                }
+                FlipbookSource::Atlas(atlas) => {
+                    if let Some(replacement) = reload.replacement(atlas) {
+                        *atlas = replacement;
+                    }
+                }
+            }
+        }
+
+        for renderer in <&mut GlyphRenderer>::query().iter_mut(world) {
+            if let Some(shader) = reload.replacement(&renderer.shader) {
+                renderer.shader = shader;
+            }
+
+            if let Some(font) = reload.replacement(renderer.font()) {
+                renderer.set_font(font);
+            }
+        }
+
+        for renderer in <&mut NinePatchRenderer>::query().iter_mut(world) {
+            if let Some(shader) = reload.replacement(&renderer.shader) {
+                renderer.shader = shader;
+            }
+
+            if let Some(nine_patch) = reload.replacement(&renderer.nine_patch) {
+                renderer.nine_patch = nine_patch;
+            }
+        }
+
+        for animator in <&mut SingleAnimator>::query().iter_mut(world) {
+            if let Some(animation) = animator
+                .animation
+                .as_ref()
+                .and_then(|animation| reload.replacement(animation))
+            {
+                animator.animation = Some(animation);
+                animator.invalid_time_lines.clear();
+            }
+        }
+
+        for animator in <&mut StateMachineAnimator>::query().iter_mut(world) {
+            let mut replaced = false;
+
+            for state in animator.states.values_mut() {
+                if let Some(animation) = reload.replacement(&state.animation) {
+                    state.animation = animation;
+                    replaced = true;
+                }
+            }
+
+            if replaced {
+                animator.invalid_time_lines.clear();
+            }
+        }
+
+        for renderer in <&mut SpriteRenderer>::query().iter_mut(world) {
+            if let Some(shader) = reload.replacement(&renderer.shader) {
+                renderer.shader = shader;
+            }
+
+            if let Some(sprite) = reload.replacement(&renderer.sprite) {
+                renderer.sprite = sprite;
+            }
+        }
+
+        for renderer in <&mut TilemapRenderer>::query().iter_mut(world) {
+            if let Some(shader) = reload.replacement(&renderer.shader) {
+                renderer.shader = shader;
+            }
+
+            if let Some(tilemap) = reload.replacement(&renderer.tilemap) {
+                renderer.tilemap = tilemap;
+            }
+        }
+    }
+}
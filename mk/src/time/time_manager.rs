@@ -1,9 +1,10 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 #[derive(Debug)]
 pub struct TimeManager {
     time: Instant,
     dt: f32,
+    fixed_dt: Option<f32>,
 }
 
 impl TimeManager {
@@ -11,6 +12,7 @@ impl TimeManager {
         TimeManager {
             time: Instant::now(),
             dt: 0f32,
+            fixed_dt: None,
         }
     }
 
@@ -22,7 +24,21 @@ impl TimeManager {
         self.dt
     }
 
+    pub fn fixed_dt(&self) -> Option<f32> {
+        self.fixed_dt
+    }
+
+    pub fn set_fixed_dt(&mut self, fixed_dt: Option<f32>) {
+        self.fixed_dt = fixed_dt;
+    }
+
     pub fn update(&mut self) {
+        if let Some(fixed_dt) = self.fixed_dt {
+            self.dt = fixed_dt;
+            self.time += Duration::from_secs_f32(fixed_dt);
+            return;
+        }
+
         let now = Instant::now();
         self.dt = (now - self.time).as_secs_f32();
         self.time = now;
@@ -1,10 +1,18 @@
 use crate::structure::Vec2;
 use crate::transform::TransformManager;
+use codegen::Animation;
 
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
+#[derive(Animation, Debug, Clone, PartialEq, PartialOrd)]
 pub struct Transform {
+    #[animate(field = "position", ty = "vec2")]
+    #[animate(field = "position.x", ty = "float", member = "x")]
+    #[animate(field = "position.y", ty = "float", member = "y")]
     pub position: Vec2,
+    #[animate(field = "scale", ty = "vec2")]
+    #[animate(field = "scale.x", ty = "float", member = "x")]
+    #[animate(field = "scale.y", ty = "float", member = "y")]
     pub scale: Vec2,
+    #[animate(field = "angle", ty = "float")]
     pub angle: f32,
     pub flags: u32,
 }
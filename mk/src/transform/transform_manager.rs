@@ -39,8 +39,16 @@ impl TransformManager {
     }
 
     pub fn dealloc(&mut self, index: u32) {
-        if let Some(name) = &self.names[index as usize] {
-            if let Some(names) = self.name_map.get_mut(name) {
+        self.set_parent(index, None);
+
+        for child in std::mem::take(&mut self.childrens[index as usize]) {
+            let transform = &mut self.transforms[child as usize];
+            transform.set_parent_index(None);
+            transform.mark_as_dirty();
+        }
+
+        if let Some(name) = self.names[index as usize].take() {
+            if let Some(names) = self.name_map.get_mut(&name) {
                 if let Some(index) = names.iter().position(|&transform| transform == index) {
                     names.swap_remove(index);
                 }
@@ -1,8 +1,19 @@
 use crate::ui::{UIAnchor, UIMargin};
+use codegen::Animation;
 
-#[derive(Debug, Clone)]
+#[derive(Animation, Debug, Clone)]
 pub struct UIElement {
+    #[animate(field = "anchor.min", ty = "vec2", member = "min")]
+    #[animate(field = "anchor.max", ty = "vec2", member = "max")]
+    #[animate(field = "anchor.min.x", ty = "float", member = "min.x")]
+    #[animate(field = "anchor.min.y", ty = "float", member = "min.y")]
+    #[animate(field = "anchor.max.x", ty = "float", member = "max.x")]
+    #[animate(field = "anchor.max.y", ty = "float", member = "max.y")]
     pub anchor: UIAnchor,
+    #[animate(field = "margin.left", ty = "float", member = "left")]
+    #[animate(field = "margin.right", ty = "float", member = "right")]
+    #[animate(field = "margin.top", ty = "float", member = "top")]
+    #[animate(field = "margin.bottom", ty = "float", member = "bottom")]
     pub margin: UIMargin,
     pub flags: u32,
 }
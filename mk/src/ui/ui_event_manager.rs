@@ -2,6 +2,8 @@ use crate::api::use_context;
 use crate::component::{Camera, Transform};
 use crate::emit_diagnostic_error;
 use crate::event::events::PerEntity;
+use crate::event::EntityEventManager;
+use crate::input::PointerButton;
 use crate::structure::Vec2;
 use glutin::event::MouseButton;
 use legion::*;
@@ -19,6 +21,13 @@ struct MouseDrag {
     button: MouseButton,
 }
 
+// A focus change requested by a script, applied once no event is being dispatched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusRequest {
+    Focus(Entity),
+    Blur(Entity),
+}
+
 #[derive(Default, Debug)]
 pub struct UIEventManager {
     pub camera: Option<Entity>,
@@ -34,10 +43,114 @@ impl UIEventManager {
         Self::default()
     }
 
+    pub fn remove_entity(&mut self, entity: Entity) {
+        if self.camera == Some(entity) {
+            self.camera = None;
+        }
+
+        if self.focus == Some(entity) {
+            self.focus = None;
+        }
+
+        if self.mouse_in == Some(entity) {
+            self.mouse_in = None;
+        }
+
+        if let Some(mouse_down) = &mut self.mouse_down {
+            if mouse_down.entity == Some(entity) {
+                mouse_down.entity = None;
+            }
+        }
+
+        if let Some(mouse_drag) = &self.mouse_drag {
+            if mouse_drag.entity == entity {
+                self.mouse_drag = None;
+            }
+        }
+    }
+
+    pub fn set_focus(&mut self, entity: Option<Entity>) {
+        if self.focus == entity {
+            return;
+        }
+
+        let context = use_context();
+
+        if let Some(focus_entity) = std::mem::replace(&mut self.focus, entity) {
+            if let Err(err) = EntityEventManager::emit(
+                context.lua_mgr().lua(),
+                &PerEntity {
+                    entity: focus_entity,
+                    event: "focus-out".to_owned(),
+                    param: MultiValue::new(),
+                },
+            ) {
+                emit_diagnostic_error!(format!(
+                    "an error occurred while emitting event '{}': {}",
+                    "focus-out", err
+                ));
+            }
+        }
+
+        if let Some(entity) = entity {
+            if let Err(err) = EntityEventManager::emit(
+                context.lua_mgr().lua(),
+                &PerEntity {
+                    entity,
+                    event: "focus-in".to_owned(),
+                    param: MultiValue::new(),
+                },
+            ) {
+                emit_diagnostic_error!(format!(
+                    "an error occurred while emitting event '{}': {}",
+                    "focus-in", err
+                ));
+            }
+        }
+    }
+
+    pub fn apply_focus_request(&mut self, request: FocusRequest) {
+        match request {
+            FocusRequest::Focus(entity) => self.set_focus(Some(entity)),
+            FocusRequest::Blur(entity) => {
+                if self.focus == Some(entity) {
+                    self.set_focus(None);
+                }
+            }
+        }
+    }
+
+    pub fn handle_text_input(text: &str) {
+        let context = use_context();
+        let focus_entity = if let Some(focus_entity) = context.ui_event_mgr().focus {
+            focus_entity
+        } else {
+            return;
+        };
+
+        if let Err(err) = EntityEventManager::emit(
+            context.lua_mgr().lua(),
+            &PerEntity {
+                entity: focus_entity,
+                event: "text-input".to_owned(),
+                param: MultiValue::from_vec(vec![LuaEventTextInput {
+                    text: text.to_owned(),
+                }
+                .to_lua(context.lua_mgr().lua())
+                .unwrap()]),
+            },
+        ) {
+            emit_diagnostic_error!(format!(
+                "an error occurred while emitting event '{}': {}",
+                "text-input", err
+            ));
+        }
+    }
+
     pub fn handle_mouse_exit(&mut self) {
         if let Some(mouse_in_entity) = self.mouse_in.take() {
             let context = use_context();
-            if let Err(err) = context.entity_event_mgr().emit(
+            if let Err(err) = EntityEventManager::emit(
                 context.lua_mgr().lua(),
                 &PerEntity {
                     entity: mouse_in_entity,
@@ -80,7 +193,7 @@ impl UIEventManager {
             Some(entity) => {
                 if let Some(mouse_in_entity) = self.mouse_in {
                     if entity != mouse_in_entity {
-                        if let Err(err) = context.entity_event_mgr().emit(
+                        if let Err(err) = EntityEventManager::emit(
                             context.lua_mgr().lua(),
                             &PerEntity {
                                 entity: mouse_in_entity,
@@ -93,7 +206,7 @@ impl UIEventManager {
                                 "mouse-exit", err
                             ));
                         }
-                        if let Err(err) = context.entity_event_mgr().emit(
+                        if let Err(err) = EntityEventManager::emit(
                             context.lua_mgr().lua(),
                             &PerEntity {
                                 entity: entity,
@@ -108,7 +221,7 @@ impl UIEventManager {
                         }
                     }
                 } else {
-                    if let Err(err) = context.entity_event_mgr().emit(
+                    if let Err(err) = EntityEventManager::emit(
                         context.lua_mgr().lua(),
                         &PerEntity {
                             entity: entity,
@@ -125,7 +238,7 @@ impl UIEventManager {
 
                 self.mouse_in = Some(entity);
 
-                if let Err(err) = context.entity_event_mgr().emit(
+                if let Err(err) = EntityEventManager::emit(
                     context.lua_mgr().lua(),
                     &PerEntity {
                         entity: entity,
@@ -143,7 +256,7 @@ impl UIEventManager {
             }
             None => {
                 if let Some(mouse_in_entity) = self.mouse_in.take() {
-                    if let Err(err) = context.entity_event_mgr().emit(
+                    if let Err(err) = EntityEventManager::emit(
                         context.lua_mgr().lua(),
                         &PerEntity {
                             entity: mouse_in_entity,
@@ -171,7 +284,7 @@ impl UIEventManager {
                     button: mouse_button,
                 });
 
-                if let Err(err) = context.entity_event_mgr().emit(
+                if let Err(err) = EntityEventManager::emit(
                     context.lua_mgr().lua(),
                     &PerEntity {
                         entity,
@@ -179,12 +292,7 @@ impl UIEventManager {
                         param: MultiValue::from_vec(vec![LuaEventDragBegin {
                             x,
                             y,
-                            button: match mouse_button {
-                                MouseButton::Left => "left",
-                                MouseButton::Right => "right",
-                                MouseButton::Middle => "middle",
-                                MouseButton::Other(_) => "other",
-                            },
+                            button: PointerButton::from(mouse_button).name(),
                         }
                         .to_lua(context.lua_mgr().lua())
                         .unwrap()]),
@@ -202,12 +310,44 @@ impl UIEventManager {
         self.last_mouse_position = Some(Vec2::new(x, y));
     }
 
+    // Scrolls the hovered element; `unit` is either "line" or "pixel".
+    // Like the text input, this only reads the manager and releases it before dispatching, so that the
+    // listeners can reach it.
+    pub fn handle_mouse_scroll(delta_x: f32, delta_y: f32, unit: &'static str) {
+        let context = use_context();
+        let mouse_in_entity = if let Some(mouse_in_entity) = context.ui_event_mgr().mouse_in {
+            mouse_in_entity
+        } else {
+            return;
+        };
+
+        if let Err(err) = EntityEventManager::emit(
+            context.lua_mgr().lua(),
+            &PerEntity {
+                entity: mouse_in_entity,
+                event: "mouse-scroll".to_owned(),
+                param: MultiValue::from_vec(vec![LuaEventMouseScroll {
+                    delta_x,
+                    delta_y,
+                    unit,
+                }
+                .to_lua(context.lua_mgr().lua())
+                .unwrap()]),
+            },
+        ) {
+            emit_diagnostic_error!(format!(
+                "an error occurred while emitting event '{}': {}",
+                "mouse-scroll", err
+            ));
+        }
+    }
+
     pub fn handle_mouse_button_down(&mut self, button: MouseButton) {
         self.mouse_down = None;
 
         if let Some(mouse_drag) = self.mouse_drag.take() {
             let context = use_context();
-            if let Err(err) = context.entity_event_mgr().emit(
+            if let Err(err) = EntityEventManager::emit(
                 context.lua_mgr().lua(),
                 &PerEntity {
                     entity: mouse_drag.entity,
@@ -222,25 +362,10 @@ impl UIEventManager {
             }
         }
 
-        let last_mouse_position = match &self.last_mouse_position {
+        let last_mouse_position = match self.last_mouse_position {
             Some(last_mouse_position) => last_mouse_position,
             None => {
-                if let Some(focus_entity) = self.focus.take() {
-                    let context = use_context();
-                    if let Err(err) = context.entity_event_mgr().emit(
-                        context.lua_mgr().lua(),
-                        &PerEntity {
-                            entity: focus_entity,
-                            event: "focus-out".to_owned(),
-                            param: MultiValue::new(),
-                        },
-                    ) {
-                        emit_diagnostic_error!(format!(
-                            "an error occurred while emitting event '{}': {}",
-                            "focus-out", err
-                        ));
-                    }
-                }
+                self.set_focus(None);
                 return;
             }
         };
@@ -278,39 +403,9 @@ impl UIEventManager {
                     button,
                 });
 
-                if let Some(focus_entity) = self.focus {
-                    if entity != focus_entity {
-                        if let Err(err) = context.entity_event_mgr().emit(
-                            context.lua_mgr().lua(),
-                            &PerEntity {
-                                entity: focus_entity,
-                                event: "focus-out".to_owned(),
-                                param: MultiValue::new(),
-                            },
-                        ) {
-                            emit_diagnostic_error!(format!(
-                                "an error occurred while emitting event '{}': {}",
-                                "focus-out", err
-                            ));
-                        }
-                        if let Err(err) = context.entity_event_mgr().emit(
-                            context.lua_mgr().lua(),
-                            &PerEntity {
-                                entity,
-                                event: "focus-in".to_owned(),
-                                param: MultiValue::new(),
-                            },
-                        ) {
-                            emit_diagnostic_error!(format!(
-                                "an error occurred while emitting event '{}': {}",
-                                "focus-in", err
-                            ));
-                        }
-                        self.focus = Some(entity);
-                    }
-                }
+                self.set_focus(Some(entity));
 
-                if let Err(err) = context.entity_event_mgr().emit(
+                if let Err(err) = EntityEventManager::emit(
                     context.lua_mgr().lua(),
                     &PerEntity {
                         entity,
@@ -318,12 +413,7 @@ impl UIEventManager {
                         param: MultiValue::from_vec(vec![LuaEventMouseDown {
                             x: last_mouse_position.x,
                             y: last_mouse_position.y,
-                            button: match button {
-                                MouseButton::Left => "left",
-                                MouseButton::Right => "right",
-                                MouseButton::Middle => "middle",
-                                MouseButton::Other(_) => "other",
-                            },
+                            button: PointerButton::from(button).name(),
                         }
                         .to_lua(context.lua_mgr().lua())
                         .unwrap()]),
@@ -341,21 +431,7 @@ impl UIEventManager {
                     button,
                 });
 
-                if let Some(focus_entity) = self.focus.take() {
-                    if let Err(err) = context.entity_event_mgr().emit(
-                        context.lua_mgr().lua(),
-                        &PerEntity {
-                            entity: focus_entity,
-                            event: "focus-out".to_owned(),
-                            param: MultiValue::new(),
-                        },
-                    ) {
-                        emit_diagnostic_error!(format!(
-                            "an error occurred while emitting event '{}': {}",
-                            "focus-out", err
-                        ));
-                    }
-                }
+                self.set_focus(None);
             }
         }
     }
@@ -368,18 +444,13 @@ impl UIEventManager {
             None => {
                 if let Some(mouse_drag) = self.mouse_drag.take() {
                     let context = use_context();
-                    if let Err(err) = context.entity_event_mgr().emit(
+                    if let Err(err) = EntityEventManager::emit(
                         context.lua_mgr().lua(),
                         &PerEntity {
                             entity: mouse_drag.entity,
                             event: "drag-end".to_owned(),
                             param: MultiValue::from_vec(vec![LuaEventDragEnd {
-                                button: match button {
-                                    MouseButton::Left => "left",
-                                    MouseButton::Right => "right",
-                                    MouseButton::Middle => "middle",
-                                    MouseButton::Other(_) => "other",
-                                },
+                                button: PointerButton::from(button).name(),
                             }
                             .to_lua(context.lua_mgr().lua())
                             .unwrap()]),
@@ -424,19 +495,14 @@ impl UIEventManager {
         match entity {
             Some(entity) => {
                 if let Some(mouse_drag) = self.mouse_drag.take() {
-                    if let Err(err) = context.entity_event_mgr().emit(
+                    if let Err(err) = EntityEventManager::emit(
                         context.lua_mgr().lua(),
                         &PerEntity {
                             entity,
                             event: "drop".to_owned(),
                             param: MultiValue::from_vec(vec![LuaEventDrop {
                                 from: crate::api::Entity::new(mouse_drag.entity),
-                                button: match button {
-                                    MouseButton::Left => "left",
-                                    MouseButton::Right => "right",
-                                    MouseButton::Middle => "middle",
-                                    MouseButton::Other(_) => "other",
-                                },
+                                button: PointerButton::from(button).name(),
                             }
                             .to_lua(context.lua_mgr().lua())
                             .unwrap()]),
@@ -447,18 +513,13 @@ impl UIEventManager {
                             "drop", err
                         ));
                     }
-                    if let Err(err) = context.entity_event_mgr().emit(
+                    if let Err(err) = EntityEventManager::emit(
                         context.lua_mgr().lua(),
                         &PerEntity {
                             entity: mouse_drag.entity,
                             event: "drag-end".to_owned(),
                             param: MultiValue::from_vec(vec![LuaEventDragEnd {
-                                button: match button {
-                                    MouseButton::Left => "left",
-                                    MouseButton::Right => "right",
-                                    MouseButton::Middle => "middle",
-                                    MouseButton::Other(_) => "other",
-                                },
+                                button: PointerButton::from(button).name(),
                             }
                             .to_lua(context.lua_mgr().lua())
                             .unwrap()]),
@@ -471,18 +532,13 @@ impl UIEventManager {
                     }
                 }
 
-                if let Err(err) = context.entity_event_mgr().emit(
+                if let Err(err) = EntityEventManager::emit(
                     context.lua_mgr().lua(),
                     &PerEntity {
                         entity,
                         event: "mouse-up".to_owned(),
                         param: MultiValue::from_vec(vec![LuaEventMouseUp {
-                            button: match button {
-                                MouseButton::Left => "left",
-                                MouseButton::Right => "right",
-                                MouseButton::Middle => "middle",
-                                MouseButton::Other(_) => "other",
-                            },
+                            button: PointerButton::from(button).name(),
                         }
                         .to_lua(context.lua_mgr().lua())
                         .unwrap()]),
@@ -496,18 +552,13 @@ impl UIEventManager {
             }
             None => {
                 if let Some(mouse_drag) = self.mouse_drag.take() {
-                    if let Err(err) = context.entity_event_mgr().emit(
+                    if let Err(err) = EntityEventManager::emit(
                         context.lua_mgr().lua(),
                         &PerEntity {
                             entity: mouse_drag.entity,
                             event: "drag-end".to_owned(),
                             param: MultiValue::from_vec(vec![LuaEventDragEnd {
-                                button: match button {
-                                    MouseButton::Left => "left",
-                                    MouseButton::Right => "right",
-                                    MouseButton::Middle => "middle",
-                                    MouseButton::Other(_) => "other",
-                                },
+                                button: PointerButton::from(button).name(),
                             }
                             .to_lua(context.lua_mgr().lua())
                             .unwrap()]),
@@ -565,6 +616,22 @@ impl<'lua> ToLua<'lua> for LuaEventMouseMove {
     }
 }
 
+pub struct LuaEventMouseScroll {
+    pub delta_x: f32,
+    pub delta_y: f32,
+    pub unit: &'static str,
+}
+
+impl<'lua> ToLua<'lua> for LuaEventMouseScroll {
+    fn to_lua(self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        let table = lua.create_table()?;
+        table.set("delta_x", self.delta_x)?;
+        table.set("delta_y", self.delta_y)?;
+        table.set("unit", self.unit)?;
+        Ok(LuaValue::Table(table))
+    }
+}
+
 pub struct LuaEventDragBegin {
     pub x: f32,
     pub y: f32,
@@ -606,3 +673,15 @@ impl<'lua> ToLua<'lua> for LuaEventDrop {
         Ok(LuaValue::Table(table))
     }
 }
+
+pub struct LuaEventTextInput {
+    pub text: String,
+}
+
+impl<'lua> ToLua<'lua> for LuaEventTextInput {
+    fn to_lua(self, lua: &'lua Lua) -> LuaResult<LuaValue<'lua>> {
+        let table = lua.create_table()?;
+        table.set("text", self.text)?;
+        Ok(LuaValue::Table(table))
+    }
+}
@@ -79,8 +79,16 @@ impl UIManager {
         let screen_mgr = context.screen_mgr();
         let transform_mgr = context.transform_mgr();
 
-        let camera_x = x - screen_mgr.width() as f32 * 0.5f32;
-        let camera_y = -y + screen_mgr.height() as f32 * 0.5f32;
+        let screen_width = screen_mgr.width() as f32;
+        let screen_height = screen_mgr.height() as f32;
+        let (camera_x, camera_y) = match camera {
+            Some((_, camera)) => {
+                let position =
+                    camera.screen_to_camera(Vec2::new(x, y), screen_width, screen_height);
+                (position.x, position.y)
+            }
+            None => (x - screen_width * 0.5f32, -y + screen_height * 0.5f32),
+        };
 
         // TODO: Capsulate below matrix calculations.
 
@@ -15,12 +15,14 @@ macro_rules! check_err {
 
 mod buffer;
 mod render_request;
+mod render_target;
 mod renderer;
 mod shader;
 mod texture;
 
 pub use buffer::*;
 pub use render_request::*;
+pub use render_target::*;
 pub use renderer::*;
 pub use shader::*;
 pub use texture::*;
@@ -53,3 +55,33 @@ pub fn resize(width: u32, height: u32) {
         gl33::Viewport(0, 0, width as _, height as _);
     }
 }
+
+// Limits both drawing and clearing to the region, given in physical pixels from the bottom left.
+pub fn set_viewport(x: i32, y: i32, width: u32, height: u32) {
+    unsafe {
+        gl33::Enable(gl33::SCISSOR_TEST);
+        gl33::Scissor(x, y, width as _, height as _);
+        gl33::Viewport(x, y, width as _, height as _);
+    }
+}
+
+pub fn reset_viewport(width: u32, height: u32) {
+    unsafe {
+        gl33::Disable(gl33::SCISSOR_TEST);
+        gl33::Viewport(0, 0, width as _, height as _);
+    }
+}
+
+pub fn clear_color(r: f32, g: f32, b: f32, a: f32) {
+    unsafe {
+        gl33::ClearColor(r, g, b, a);
+        gl33::Clear(gl33::COLOR_BUFFER_BIT);
+        gl33::ClearColor(0f32, 0f32, 0f32, 0f32);
+    }
+}
+
+pub fn clear_depth() {
+    unsafe {
+        gl33::Clear(gl33::DEPTH_BUFFER_BIT);
+    }
+}
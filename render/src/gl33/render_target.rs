@@ -0,0 +1,96 @@
+use crate::{NativeHandle, Object, Texture};
+use gl33::types::*;
+use std::sync::Arc;
+
+// A framebuffer that renders into a texture instead of the screen.
+#[derive(Debug)]
+pub struct RenderTarget {
+    handle: GLuint,
+    texture: Arc<Texture>,
+}
+
+impl RenderTarget {
+    pub fn with_size_rgba_u8(width: u32, height: u32) -> Result<Self, String> {
+        let texture = Arc::new(Texture::with_size_rgba_u8(width, height));
+        let handle = ptr_init!(ptr => gl33::GenFramebuffers(1, ptr));
+        let status;
+
+        unsafe {
+            gl33::BindFramebuffer(gl33::FRAMEBUFFER, handle);
+            check_err!();
+            gl33::FramebufferTexture2D(
+                gl33::FRAMEBUFFER,
+                gl33::COLOR_ATTACHMENT0,
+                gl33::TEXTURE_2D,
+                texture.handle().0,
+                0,
+            );
+            check_err!();
+            status = gl33::CheckFramebufferStatus(gl33::FRAMEBUFFER);
+            check_err!();
+
+            // The texture is allocated without data, so it's cleared to transparent before its first use.
+            if status == gl33::FRAMEBUFFER_COMPLETE {
+                gl33::ClearColor(0f32, 0f32, 0f32, 0f32);
+                gl33::Clear(gl33::COLOR_BUFFER_BIT);
+                check_err!();
+            }
+
+            gl33::BindFramebuffer(gl33::FRAMEBUFFER, 0);
+            check_err!();
+        }
+
+        let render_target = RenderTarget { handle, texture };
+
+        if status != gl33::FRAMEBUFFER_COMPLETE {
+            return Err(format!(
+                "failed to create render target (handle={}, status={})",
+                handle, status
+            ));
+        }
+
+        Ok(render_target)
+    }
+
+    pub fn texture(&self) -> &Arc<Texture> {
+        &self.texture
+    }
+
+    pub fn width(&self) -> u32 {
+        self.texture.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.texture.height()
+    }
+
+    pub fn bind(&self) {
+        unsafe {
+            gl33::BindFramebuffer(gl33::FRAMEBUFFER, self.handle);
+            check_err!();
+        }
+    }
+
+    // Makes the screen the render target again.
+    pub fn bind_default() {
+        unsafe {
+            gl33::BindFramebuffer(gl33::FRAMEBUFFER, 0);
+            check_err!();
+        }
+    }
+}
+
+impl Drop for RenderTarget {
+    fn drop(&mut self) {
+        unsafe {
+            gl33::DeleteFramebuffers(1, &self.handle as _);
+            check_err!();
+        }
+    }
+}
+
+impl Object for RenderTarget {
+    fn handle(&self) -> NativeHandle {
+        NativeHandle(self.handle)
+    }
+}
@@ -1,5 +1,5 @@
 use crate::io::read_file_all;
-use crate::{chunk_to_filename, Resource};
+use crate::{chunk_to_filename, Resource, ResourceMeta};
 use aes::cipher::generic_array::GenericArray;
 use aes::cipher::{FromBlockCipher, NewBlockCipher, StreamCipher, StreamCipherSeek};
 use aes::{Aes256, Aes256Ctr};
@@ -153,15 +153,19 @@ impl ResourceLoader {
         cipher.seek(res.cipher_offset);
         cipher.apply_keystream(&mut content);
 
-        Ok(decoder.decode(content)?)
+        Ok(decoder.decode(res.meta.as_ref(), content)?)
     }
 }
 
-pub type DecoderError = Box<dyn Error>;
+pub type DecoderError = Box<dyn Error + Send + Sync>;
 
-pub trait ResourceDecoder {
+pub trait ResourceDecoder: Send + Sync {
     fn ty(&self) -> &str;
-    fn decode(&self, content: Vec<u8>) -> Result<Arc<dyn BaseResource>, DecoderError>;
+    fn decode(
+        &self,
+        meta: Option<&ResourceMeta>,
+        content: Vec<u8>,
+    ) -> Result<Arc<dyn BaseResource>, DecoderError>;
 }
 
 pub trait BaseResource: Downcast {
@@ -1,3 +1,5 @@
+mod sprite;
 mod text;
 
+pub use sprite::*;
 pub use text::*;
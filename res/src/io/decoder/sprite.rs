@@ -0,0 +1,118 @@
+use crate::asset_loader::{BaseResource, DecoderError, ResourceDecoder};
+use crate::{ResourceMeta, ResourceMetaValue, SpriteChannel};
+use brotli::DecompressorWriter;
+#[cfg(feature = "image_decoder")]
+use image::{load_from_memory, ColorType, GenericImageView, ImageError};
+use std::io::Write;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpriteDecoderOutput {
+    pub width: u32,
+    pub height: u32,
+    pub channel: SpriteChannel,
+    pub pixels: Vec<u8>,
+}
+
+impl BaseResource for SpriteDecoderOutput {
+    fn ty(&self) -> &str {
+        "sprite"
+    }
+}
+
+pub struct SpriteDecoder;
+
+impl ResourceDecoder for SpriteDecoder {
+    fn ty(&self) -> &str {
+        "sprite"
+    }
+
+    fn decode(
+        &self,
+        meta: Option<&ResourceMeta>,
+        content: Vec<u8>,
+    ) -> Result<Arc<dyn BaseResource>, DecoderError> {
+        let meta = meta.ok_or("sprite resource has no meta")?;
+        let integer = |key: &str| match meta.get(key) {
+            Some(ResourceMetaValue::Integer(integer)) => Ok(*integer as u32),
+            _ => Err(format!("sprite resource has no valid '{}' meta", key)),
+        };
+        let width = integer("width")?;
+        let height = integer("height")?;
+        let channel = match meta.get("channel") {
+            Some(ResourceMetaValue::String(channel)) => channel.parse::<SpriteChannel>().ok(),
+            _ => None,
+        }
+        .ok_or("sprite resource has no valid 'channel' meta")?;
+        let compression = match meta.get("compression") {
+            Some(ResourceMetaValue::Boolean(compression)) => *compression,
+            _ => false,
+        };
+
+        let pixels = if compression {
+            let mut result = vec![];
+
+            {
+                let mut writer = DecompressorWriter::new(&mut result, 4096);
+                writer.write_all(&content)?;
+            }
+
+            result
+        } else {
+            content
+        };
+
+        let size = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|size| size.checked_mul(channel.component() as usize))
+            .ok_or_else(|| format!("sprite resource of {}x{} is too large", width, height))?;
+
+        if pixels.len() != size {
+            return Err(format!(
+                "sprite resource has {} bytes of pixels, but {}x{} {} requires {} bytes",
+                pixels.len(),
+                width,
+                height,
+                channel.to_str(),
+                size
+            )
+            .into());
+        }
+
+        Ok(Arc::new(SpriteDecoderOutput {
+            width,
+            height,
+            channel,
+            pixels,
+        }))
+    }
+}
+
+// Decodes an image file; the channel is chosen from the image's color type if not given.
+#[cfg(feature = "image_decoder")]
+pub fn decode_image(
+    image: &[u8],
+    channel: Option<SpriteChannel>,
+) -> Result<SpriteDecoderOutput, ImageError> {
+    let image = load_from_memory(image)?;
+    let (width, height) = image.dimensions();
+    let channel = channel.unwrap_or_else(|| match image.color() {
+        ColorType::L8 | ColorType::L16 => SpriteChannel::R,
+        ColorType::La8 | ColorType::La16 => SpriteChannel::RG,
+        ColorType::Rgb8 | ColorType::Rgb16 | ColorType::Rgb32F => SpriteChannel::RGB,
+        _ => SpriteChannel::RGBA,
+    });
+    let pixels = match channel {
+        SpriteChannel::R => image.into_luma8().into_raw(),
+        SpriteChannel::RG => image.into_luma_alpha8().into_raw(),
+        SpriteChannel::RGB => image.into_rgb8().into_raw(),
+        SpriteChannel::RGBA => image.into_rgba8().into_raw(),
+    };
+
+    Ok(SpriteDecoderOutput {
+        width,
+        height,
+        channel,
+        pixels,
+    })
+}
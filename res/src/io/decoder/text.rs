@@ -1,4 +1,5 @@
 use crate::asset_loader::{BaseResource, DecoderError, ResourceDecoder};
+use crate::ResourceMeta;
 use brotli::DecompressorWriter;
 use std::io::Write;
 use std::sync::Arc;
@@ -21,7 +22,11 @@ impl ResourceDecoder for TextDecoder {
         "text"
     }
 
-    fn decode(&self, content: Vec<u8>) -> Result<Arc<dyn BaseResource>, DecoderError> {
+    fn decode(
+        &self,
+        _meta: Option<&ResourceMeta>,
+        content: Vec<u8>,
+    ) -> Result<Arc<dyn BaseResource>, DecoderError> {
         let mut result = vec![];
 
         {
@@ -0,0 +1,109 @@
+use crate::decoder::{decode_image, SpriteDecoderOutput};
+use crate::writer::{
+    compute_resource_hash, EncodedResource, EncoderError, ResourceEncoder,
+    ResourceEncoderDirectoryManager,
+};
+use crate::{ResourceMeta, ResourceMetaValue, ResourceUUID};
+use brotli::CompressorWriter;
+use memmap2::Mmap;
+use std::io::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SpriteEncoder {
+    pub compression: bool,
+    pub q: u32,
+    pub lgwin: u32,
+}
+
+impl SpriteEncoder {
+    pub fn new(compression: bool, q: u32, lgwin: u32) -> Self {
+        Self {
+            compression,
+            q,
+            lgwin,
+        }
+    }
+}
+
+impl Default for SpriteEncoder {
+    fn default() -> Self {
+        Self {
+            compression: true,
+            q: 9,
+            lgwin: 22,
+        }
+    }
+}
+
+impl ResourceEncoder for SpriteEncoder {
+    fn ty(&self) -> &str {
+        "sprite"
+    }
+
+    fn encode(
+        &self,
+        dir_mgr: &dyn ResourceEncoderDirectoryManager,
+        uuid: ResourceUUID,
+        src: Mmap,
+    ) -> Result<EncodedResource, EncoderError> {
+        let SpriteDecoderOutput {
+            width,
+            height,
+            channel,
+            pixels,
+        } = decode_image(&src, None)?;
+
+        let mut file = dir_mgr.alloc_tmp_file(uuid)?;
+
+        if self.compression {
+            let mut writer = CompressorWriter::new(&mut file, 4096, self.q, self.lgwin);
+            writer.write_all(&pixels)?;
+        } else {
+            file.write_all(&pixels)?;
+        }
+
+        let content = unsafe { Mmap::map(&file) }?;
+        let mut meta = ResourceMeta::new();
+        meta.insert("width".to_owned(), ResourceMetaValue::Integer(width as _));
+        meta.insert("height".to_owned(), ResourceMetaValue::Integer(height as _));
+        meta.insert(
+            "channel".to_owned(),
+            ResourceMetaValue::String(channel.to_str().to_owned()),
+        );
+        meta.insert(
+            "compression".to_owned(),
+            ResourceMetaValue::Boolean(self.compression),
+        );
+        // The resource hash covers the source image, which can't be restored from the pixels.
+        meta.insert(
+            "pixels_hash".to_owned(),
+            ResourceMetaValue::String(compute_resource_hash(&pixels).hash),
+        );
+
+        if self.compression {
+            meta.insert(
+                "algorithm".to_owned(),
+                ResourceMetaValue::String("brotli".to_owned()),
+            );
+            meta.insert("q".to_owned(), ResourceMetaValue::Integer(self.q as _));
+            meta.insert(
+                "lgwin".to_owned(),
+                ResourceMetaValue::Integer(self.lgwin as _),
+            );
+        }
+
+        meta.insert(
+            "size_before".to_owned(),
+            ResourceMetaValue::Integer(src.len() as _),
+        );
+        meta.insert(
+            "size_after".to_owned(),
+            ResourceMetaValue::Integer(content.len() as _),
+        );
+
+        Ok(EncodedResource {
+            meta: Some(meta),
+            content,
+        })
+    }
+}
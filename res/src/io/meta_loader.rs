@@ -1,6 +1,7 @@
 use crate::ResourcesMeta;
 use bincode::{options, Error as BincodeError, Options};
 
+#[derive(Debug)]
 pub enum MetaLoadError {
     BincodeError(BincodeError),
     UnsupportedVersion,
@@ -0,0 +1,22 @@
+use crate::ResourcesMeta;
+use bincode::{options, Error as BincodeError, Options};
+
+#[derive(Debug)]
+pub enum MetaWriteError {
+    BincodeError(BincodeError),
+}
+
+impl From<BincodeError> for MetaWriteError {
+    fn from(err: BincodeError) -> Self {
+        Self::BincodeError(err)
+    }
+}
+
+pub fn write_resource_meta(meta: &ResourcesMeta) -> Result<Vec<u8>, MetaWriteError> {
+    Ok(options()
+        .with_no_limit()
+        .with_little_endian()
+        .with_varint_encoding()
+        .reject_trailing_bytes()
+        .serialize(meta)?)
+}
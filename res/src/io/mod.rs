@@ -9,10 +9,15 @@ pub mod meta_loader;
 #[cfg(feature = "writer")]
 pub mod encoder;
 #[cfg(feature = "writer")]
+pub mod meta_writer;
+#[cfg(feature = "writer")]
 pub mod writer;
 
 use std::io::Error as IOError;
 
+#[cfg(any(feature = "asset_loader", feature = "meta_loader", feature = "writer"))]
+pub const META_FILENAME: &str = "assets.meta";
+
 #[cfg(any(feature = "asset_loader", feature = "writer"))]
 pub(in crate) fn chunk_to_filename(chunk: crate::ResourceChunkID) -> String {
     format!("assets{}.res", chunk)
@@ -89,18 +89,7 @@ impl ResourceWriter {
                     .map_err(|err| ResourceWriteError::CannotMapResourceFile(err))?;
 
                 let uuid = unsafe { ResourceUUID::new_unchecked((index + 1) as u64) };
-                let hash = ResourceHash {
-                    hash: {
-                        let mut hasher = Crc32Hasher::new();
-                        hasher.update(&content);
-
-                        let buffer = &mut [0u8; size_of::<u32>()];
-                        LittleEndian::write_u32(buffer, hasher.finalize());
-
-                        sha256_digest_bytes(buffer)
-                    },
-                    algorithm: ResourceHashAlgorithm::CRC32LESHA256,
-                };
+                let hash = compute_resource_hash(&content);
                 let encoded = encoder.encode(&dir_mgr, uuid, content)?;
 
                 Ok((uuid, hash, encoded))
@@ -250,7 +239,7 @@ impl ResourceWriter {
                         chunk_offset..chunk_offset + len
                     }];
 
-                    range.copy_from_slice(&content[..len]);
+                    range.copy_from_slice(&content[content_offset..content_offset + len]);
 
                     if apply_cipher {
                         cipher.apply_keystream(range);
@@ -312,6 +301,21 @@ impl ResourceWriter {
     }
 }
 
+pub fn compute_resource_hash(content: &[u8]) -> ResourceHash {
+    ResourceHash {
+        hash: {
+            let mut hasher = Crc32Hasher::new();
+            hasher.update(content);
+
+            let buffer = &mut [0u8; size_of::<u32>()];
+            LittleEndian::write_u32(buffer, hasher.finalize());
+
+            sha256_digest_bytes(buffer)
+        },
+        algorithm: ResourceHashAlgorithm::CRC32LESHA256,
+    }
+}
+
 pub trait ResourceEncoder: Send + Sync {
     fn ty(&self) -> &str;
     fn encode(
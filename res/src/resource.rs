@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::num::NonZeroU64;
+use std::str::FromStr;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Hash)]
 pub struct ResourcesMeta {
@@ -51,3 +52,45 @@ pub enum ResourceMetaValue {
     Integer(i64),
     String(String),
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpriteChannel {
+    R,
+    RG,
+    RGB,
+    RGBA,
+}
+
+impl FromStr for SpriteChannel {
+    type Err = String;
+
+    fn from_str(channel: &str) -> Result<Self, Self::Err> {
+        match channel {
+            "r" => Ok(Self::R),
+            "rg" => Ok(Self::RG),
+            "rgb" => Ok(Self::RGB),
+            "rgba" => Ok(Self::RGBA),
+            _ => Err(format!("{:?} is not a sprite channel", channel)),
+        }
+    }
+}
+
+impl SpriteChannel {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Self::R => "r",
+            Self::RG => "rg",
+            Self::RGB => "rgb",
+            Self::RGBA => "rgba",
+        }
+    }
+
+    pub fn component(self) -> u32 {
+        match self {
+            Self::R => 1,
+            Self::RG => 2,
+            Self::RGB => 3,
+            Self::RGBA => 4,
+        }
+    }
+}
@@ -1 +1,500 @@
-fn main() {}
+mod manifest;
+
+use clap::{Parser, Subcommand};
+use image::{save_buffer_with_format, ColorType, ImageError, ImageFormat};
+use manifest::{Manifest, ManifestError};
+use res::asset_loader::{ResourceLoadError, ResourceLoader};
+use res::decoder::{SpriteDecoder, SpriteDecoderOutput, TextDecoder, TextDecoderOutput};
+use res::encoder::{SpriteEncoder, TextEncoder};
+use res::meta_loader::{load_resource_meta, MetaLoadError};
+use res::meta_writer::{write_resource_meta, MetaWriteError};
+use res::writer::{compute_resource_hash, ResourceWriteError, ResourceWriter, WritingResource};
+use res::{Resource, ResourceMetaValue, ResourcesMeta, SpriteChannel, META_FILENAME};
+use std::env::var as env_var;
+use std::ffi::OsString;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::fs::{create_dir_all, read as fs_read, read_to_string, write as fs_write};
+use std::io::Error as IOError;
+use std::path::{Component, Path, PathBuf};
+use std::process::exit;
+
+const KEY_ENV: &str = "RESC_KEY";
+const SALT_ENV: &str = "RESC_SALT";
+
+#[derive(Debug)]
+enum RescError {
+    IOError(IOError),
+    ManifestError(ManifestError),
+    MetaLoadError(MetaLoadError),
+    MetaWriteError(MetaWriteError),
+    ResourceWriteError(ResourceWriteError),
+    ResourceLoadError(ResourceLoadError),
+    ImageError(ImageError),
+    MissingSecret(String),
+    UnextractableResource(String),
+    UnsafeResourceName(String),
+    VerificationFailed(usize),
+}
+
+impl Display for RescError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Self::IOError(err) => write!(f, "{}", err),
+            Self::ManifestError(ManifestError::IOError(err)) => {
+                write!(f, "cannot read the manifest: {}", err)
+            }
+            Self::ManifestError(ManifestError::JSONError(err)) => {
+                write!(f, "invalid manifest: {}", err)
+            }
+            Self::MetaLoadError(MetaLoadError::BincodeError(err)) => {
+                write!(f, "cannot read the resource meta: {}", err)
+            }
+            Self::MetaLoadError(MetaLoadError::UnsupportedVersion) => {
+                write!(f, "the resource meta has an unsupported version")
+            }
+            Self::MetaWriteError(MetaWriteError::BincodeError(err)) => {
+                write!(f, "cannot write the resource meta: {}", err)
+            }
+            Self::ResourceWriteError(err) => match err {
+                ResourceWriteError::UnknownResourceType => {
+                    write!(f, "the manifest has a resource of unknown type")
+                }
+                ResourceWriteError::CannotPrepareDirectory(err) => {
+                    write!(f, "cannot prepare the output directory: {}", err)
+                }
+                ResourceWriteError::CannotOpenResourceFile(err)
+                | ResourceWriteError::CannotMapResourceFile(err) => {
+                    write!(f, "cannot read a resource file: {}", err)
+                }
+                ResourceWriteError::CannotCreateChunkFile(err)
+                | ResourceWriteError::CannotMapChunkFile(err) => {
+                    write!(f, "cannot write a chunk file: {}", err)
+                }
+                ResourceWriteError::CannotCleanupTempDirectory(err) => {
+                    write!(f, "cannot remove the temporary directory: {}", err)
+                }
+                ResourceWriteError::CipherKeyGenError(err) => {
+                    write!(f, "cannot generate the cipher key: {}", err)
+                }
+                ResourceWriteError::KeySaltHashError(err) => {
+                    write!(f, "cannot hash the key and the salt: {}", err)
+                }
+                ResourceWriteError::EncoderError(err) => {
+                    write!(f, "cannot encode a resource: {}", err)
+                }
+            },
+            Self::ResourceLoadError(err) => match err {
+                ResourceLoadError::UnknownResourceType => {
+                    write!(f, "the pack has a resource of unknown type")
+                }
+                ResourceLoadError::IOError(err)
+                | ResourceLoadError::CannotOpenResourceFile(err) => {
+                    write!(f, "cannot read a chunk file: {}", err)
+                }
+                ResourceLoadError::DecoderError(err) => {
+                    write!(f, "cannot decode a resource: {}", err)
+                }
+                ResourceLoadError::BasePathNotFound(err) => {
+                    write!(f, "cannot find the packed directory: {}", err)
+                }
+                ResourceLoadError::KeySaltHashError(err) => {
+                    write!(f, "cannot hash the key and the salt: {}", err)
+                }
+            },
+            Self::ImageError(err) => write!(f, "cannot write an image: {}", err),
+            Self::MissingSecret(message) => write!(f, "{}", message),
+            Self::UnextractableResource(name) => write!(
+                f,
+                "resource '{}' is of a type that cannot be extracted",
+                name
+            ),
+            Self::UnsafeResourceName(name) => write!(
+                f,
+                "resource '{}' has a name that would be extracted outside the output directory",
+                name
+            ),
+            Self::VerificationFailed(failed) => {
+                write!(f, "{} resource(s) failed verification", failed)
+            }
+        }
+    }
+}
+
+impl From<IOError> for RescError {
+    fn from(err: IOError) -> Self {
+        Self::IOError(err)
+    }
+}
+
+impl From<ManifestError> for RescError {
+    fn from(err: ManifestError) -> Self {
+        Self::ManifestError(err)
+    }
+}
+
+impl From<MetaLoadError> for RescError {
+    fn from(err: MetaLoadError) -> Self {
+        Self::MetaLoadError(err)
+    }
+}
+
+impl From<MetaWriteError> for RescError {
+    fn from(err: MetaWriteError) -> Self {
+        Self::MetaWriteError(err)
+    }
+}
+
+impl From<ResourceWriteError> for RescError {
+    fn from(err: ResourceWriteError) -> Self {
+        Self::ResourceWriteError(err)
+    }
+}
+
+impl From<ResourceLoadError> for RescError {
+    fn from(err: ResourceLoadError) -> Self {
+        Self::ResourceLoadError(err)
+    }
+}
+
+impl From<ImageError> for RescError {
+    fn from(err: ImageError) -> Self {
+        Self::ImageError(err)
+    }
+}
+
+/// The key and the salt are read from the RESC_KEY and RESC_SALT environment variables, or from
+/// the file given by `--key-file` with the key on the first line and the salt on the second.
+#[derive(Parser, Debug)]
+#[clap(name = "resc", version, about = "Resource compiler for mk")]
+struct Args {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Packs all resources listed in the manifest into the output directory.
+    Pack {
+        manifest: PathBuf,
+        output: PathBuf,
+        /// File holding the key and the salt; the salt must be at least 8 bytes long.
+        #[clap(long)]
+        key_file: Option<PathBuf>,
+        /// Maximum size of a chunk file in bytes; a single chunk is used if omitted.
+        #[clap(long)]
+        chunk_size: Option<u64>,
+    },
+    /// Lists all resources in the packed directory.
+    List { input: PathBuf },
+    /// Decodes all resources in the packed directory and checks their hashes.
+    Verify {
+        input: PathBuf,
+        /// File holding the key and the salt.
+        #[clap(long)]
+        key_file: Option<PathBuf>,
+    },
+    /// Extracts resources from the packed directory.
+    Extract {
+        input: PathBuf,
+        output: PathBuf,
+        /// File holding the key and the salt.
+        #[clap(long)]
+        key_file: Option<PathBuf>,
+        /// Names of the resources to extract; all resources are extracted if omitted.
+        names: Vec<String>,
+    },
+}
+
+fn main() {
+    if let Err(err) = run(Args::parse().command) {
+        eprintln!("error: {}", err);
+        exit(1);
+    }
+}
+
+fn run(command: Command) -> Result<(), RescError> {
+    match command {
+        Command::Pack {
+            manifest,
+            output,
+            key_file,
+            chunk_size,
+        } => {
+            let (key, salt) = read_secrets(key_file.as_deref())?;
+            pack(&manifest, &output, &key, &salt, chunk_size)
+        }
+        Command::List { input } => list(&input),
+        Command::Verify { input, key_file } => {
+            let (key, salt) = read_secrets(key_file.as_deref())?;
+            verify(&input, &key, &salt)
+        }
+        Command::Extract {
+            input,
+            output,
+            key_file,
+            names,
+        } => {
+            let (key, salt) = read_secrets(key_file.as_deref())?;
+            extract(&input, &output, &key, &salt, &names)
+        }
+    }
+}
+
+// Secrets are kept off the command line, where they would end up in the shell history.
+fn read_secrets(key_file: Option<&Path>) -> Result<(String, String), RescError> {
+    if let Some(key_file) = key_file {
+        let content = read_to_string(key_file)?;
+        let mut lines = content.lines();
+
+        return match (lines.next(), lines.next()) {
+            (Some(key), Some(salt)) => Ok((key.to_owned(), salt.to_owned())),
+            _ => Err(RescError::MissingSecret(format!(
+                "'{}' must have the key on the first line and the salt on the second",
+                key_file.display()
+            ))),
+        };
+    }
+
+    let read_env = |name: &str| {
+        env_var(name).map_err(|_| {
+            RescError::MissingSecret(format!(
+                "set {} and {}, or pass --key-file",
+                KEY_ENV, SALT_ENV
+            ))
+        })
+    };
+
+    Ok((read_env(KEY_ENV)?, read_env(SALT_ENV)?))
+}
+
+fn resource_writer() -> ResourceWriter {
+    let mut writer = ResourceWriter::new();
+    writer.add_encoder("sprite".to_owned(), Box::new(SpriteEncoder::default()));
+    writer.add_encoder("text".to_owned(), Box::new(TextEncoder::default()));
+    writer
+}
+
+fn resource_loader(input: &Path, key: &str, salt: &str) -> Result<ResourceLoader, RescError> {
+    let mut loader = ResourceLoader::new(key, salt, input)?;
+    loader.add_decoder(Box::new(SpriteDecoder));
+    loader.add_decoder(Box::new(TextDecoder));
+    Ok(loader)
+}
+
+fn read_meta(input: &Path) -> Result<ResourcesMeta, RescError> {
+    Ok(load_resource_meta(&fs_read(input.join(META_FILENAME))?)?)
+}
+
+fn pack(
+    manifest: &Path,
+    output: &Path,
+    key: &str,
+    salt: &str,
+    chunk_size: Option<u64>,
+) -> Result<(), RescError> {
+    let manifest = Manifest::from_file(manifest)?;
+    let resources = manifest
+        .resources
+        .iter()
+        .map(|item| WritingResource {
+            name: &item.name,
+            ty: &item.ty,
+            path: &item.path,
+        })
+        .collect::<Vec<_>>();
+    let meta = resource_writer().write(key, salt, chunk_size, output, resources)?;
+
+    fs_write(output.join(META_FILENAME), write_resource_meta(&meta)?)?;
+
+    let mut total_before = 0;
+    let mut total_after = 0;
+
+    for res in &meta.resources {
+        let integer = |key: &str| match res.meta.as_ref().and_then(|meta| meta.get(key)) {
+            Some(ResourceMetaValue::Integer(integer)) => Some(*integer as u64),
+            _ => None,
+        };
+        let size_before = integer("size_before").unwrap_or(res.size);
+        let size_after = integer("size_after").unwrap_or(res.size);
+
+        total_before += size_before;
+        total_after += size_after;
+
+        println!(
+            "{:<40} {:<8} {:>12} -> {:>12} ({:>6.2}%)",
+            res.name,
+            res.ty,
+            size_before,
+            size_after,
+            ratio(size_before, size_after)
+        );
+    }
+
+    println!(
+        "packed {} resources into {} chunk(s): {} -> {} bytes ({:.2}%)",
+        meta.resources.len(),
+        meta.resources
+            .iter()
+            .flat_map(|res| res.chunks.iter().map(|chunk| chunk.id))
+            .max()
+            .map_or(1, |id| id + 1),
+        total_before,
+        total_after,
+        ratio(total_before, total_after)
+    );
+
+    Ok(())
+}
+
+fn list(input: &Path) -> Result<(), RescError> {
+    let meta = read_meta(input)?;
+
+    for res in &meta.resources {
+        println!(
+            "{:<40} {:<8} {:>12} bytes in {} chunk(s)",
+            res.name,
+            res.ty,
+            res.size,
+            res.chunks.len()
+        );
+    }
+
+    Ok(())
+}
+
+fn verify(input: &Path, key: &str, salt: &str) -> Result<(), RescError> {
+    let meta = read_meta(input)?;
+    let loader = resource_loader(input, key, salt)?;
+    let mut failed = 0;
+
+    for res in &meta.resources {
+        let status = match loader.load(res) {
+            Ok(decoded) => {
+                let matches = if let Some(output) = decoded.downcast_ref::<TextDecoderOutput>() {
+                    Ok(compute_resource_hash(&output.content) == res.hash)
+                } else if let Some(output) = decoded.downcast_ref::<SpriteDecoderOutput>() {
+                    match pixels_hash(res) {
+                        Some(hash) => Ok(compute_resource_hash(&output.pixels).hash == hash),
+                        None => Err("no pixel hash to check against".to_owned()),
+                    }
+                } else {
+                    Err("no hash to check against".to_owned())
+                };
+
+                match matches {
+                    Ok(true) => "ok".to_owned(),
+                    Ok(false) => {
+                        failed += 1;
+                        "hash mismatch".to_owned()
+                    }
+                    Err(reason) => {
+                        failed += 1;
+                        format!("unverified: {}", reason)
+                    }
+                }
+            }
+            Err(err) => {
+                failed += 1;
+                format!("error: {}", RescError::from(err))
+            }
+        };
+
+        println!("{:<40} {}", res.name, status);
+    }
+
+    if failed != 0 {
+        return Err(RescError::VerificationFailed(failed));
+    }
+
+    Ok(())
+}
+
+fn extract(
+    input: &Path,
+    output: &Path,
+    key: &str,
+    salt: &str,
+    names: &[String],
+) -> Result<(), RescError> {
+    let meta = read_meta(input)?;
+    let loader = resource_loader(input, key, salt)?;
+
+    for res in &meta.resources {
+        if !names.is_empty() && !names.contains(&res.name) {
+            continue;
+        }
+
+        // The names come from the pack, so they must not escape the output directory.
+        let name = Path::new(&res.name);
+
+        if name.as_os_str().is_empty()
+            || !name
+                .components()
+                .all(|component| matches!(component, Component::Normal(..)))
+        {
+            return Err(RescError::UnsafeResourceName(res.name.clone()));
+        }
+
+        let decoded = loader.load(res)?;
+        let output_path = output.join(name);
+
+        if let Some(parent) = output_path.parent() {
+            create_dir_all(parent)?;
+        }
+
+        let output_path = if let Some(decoded) = decoded.downcast_ref::<TextDecoderOutput>() {
+            fs_write(&output_path, &decoded.content)?;
+            output_path
+        } else if let Some(decoded) = decoded.downcast_ref::<SpriteDecoderOutput>() {
+            // Sprites are packed as raw pixels, so they're extracted as PNG images.
+            let output_path = png_path(output_path);
+            let color = match decoded.channel {
+                SpriteChannel::R => ColorType::L8,
+                SpriteChannel::RG => ColorType::La8,
+                SpriteChannel::RGB => ColorType::Rgb8,
+                SpriteChannel::RGBA => ColorType::Rgba8,
+            };
+
+            save_buffer_with_format(
+                &output_path,
+                &decoded.pixels,
+                decoded.width,
+                decoded.height,
+                color,
+                ImageFormat::Png,
+            )?;
+            output_path
+        } else {
+            return Err(RescError::UnextractableResource(res.name.clone()));
+        };
+
+        println!("{} -> {}", res.name, output_path.display());
+    }
+
+    Ok(())
+}
+
+fn pixels_hash(res: &Resource) -> Option<&str> {
+    match res.meta.as_ref()?.get("pixels_hash")? {
+        ResourceMetaValue::String(hash) => Some(hash),
+        _ => None,
+    }
+}
+
+fn png_path(path: PathBuf) -> PathBuf {
+    if path.extension().is_some_and(|extension| extension == "png") {
+        return path;
+    }
+
+    let mut file_name = path.file_name().map(OsString::from).unwrap_or_default();
+    file_name.push(".png");
+    path.with_file_name(file_name)
+}
+
+fn ratio(before: u64, after: u64) -> f64 {
+    if before == 0 {
+        100f64
+    } else {
+        after as f64 / before as f64 * 100f64
+    }
+}
@@ -0,0 +1,52 @@
+use serde::Deserialize;
+use serde_json::{from_str, Error as JSONError};
+use std::fs::read_to_string;
+use std::io::Error as IOError;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum ManifestError {
+    IOError(IOError),
+    JSONError(JSONError),
+}
+
+impl From<IOError> for ManifestError {
+    fn from(err: IOError) -> Self {
+        Self::IOError(err)
+    }
+}
+
+impl From<JSONError> for ManifestError {
+    fn from(err: JSONError) -> Self {
+        Self::JSONError(err)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ManifestItem {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub path: PathBuf,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Manifest {
+    pub resources: Vec<ManifestItem>,
+}
+
+impl Manifest {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ManifestError> {
+        let path = path.as_ref();
+        let mut manifest: Manifest = from_str(&read_to_string(path)?)?;
+
+        // Resource paths are relative to the manifest itself.
+        if let Some(base) = path.parent() {
+            for item in &mut manifest.resources {
+                item.path = base.join(&item.path);
+            }
+        }
+
+        Ok(manifest)
+    }
+}